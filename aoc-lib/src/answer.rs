@@ -0,0 +1,104 @@
+//! Puzzle answers computed with overflow detection.
+//!
+//! Answers are accumulated as [`Answer`] with the checked helpers of this
+//! module, so that an overflow is reported as an [`Error::OverflowError`]
+//! instead of silently giving a wrong answer in release builds. To also catch
+//! overflows in intermediate computations, run a day with the `checked`
+//! profile, which keeps optimizations but enables overflow checks:
+//! `cargo run --profile checked`.
+use crate::math::Integer;
+use crate::{Error, Result};
+
+/// Integer type of puzzle answers, wide enough to hold any product of two `usize`.
+pub type Answer = u128;
+
+/// Add `b` to `a`, `what` describing the computation in the error.
+pub fn checked_add<T: Integer>(a: T, b: T, what: &str) -> Result<T> {
+    a.checked_add(b)
+        .ok_or_else(|| Error::OverflowError(format!("{what}: {a:?} + {b:?}")))
+}
+
+/// Multiply `a` by `b`, `what` describing the computation in the error.
+pub fn checked_mul<T: Integer>(a: T, b: T, what: &str) -> Result<T> {
+    a.checked_mul(b)
+        .ok_or_else(|| Error::OverflowError(format!("{what}: {a:?} * {b:?}")))
+}
+
+/// Sum and product of iterators of integers, reporting overflows as errors.
+pub trait CheckedIterator: Iterator<Item: Integer> + Sized {
+    /// Sum all values, `what` describing the computation in the error.
+    fn checked_sum(mut self, what: &str) -> Result<Self::Item> {
+        self.try_fold(Self::Item::ZERO, |sum, value| checked_add(sum, value, what))
+    }
+
+    /// Multiply all values, `what` describing the computation in the error.
+    fn checked_product(mut self, what: &str) -> Result<Self::Item> {
+        self.try_fold(Self::Item::ONE, |product, value| {
+            checked_mul(product, value, what)
+        })
+    }
+}
+
+impl<I: Iterator<Item: Integer>> CheckedIterator for I {}
+
+/// Sum of iterators of fallible integers, stopping at the first error.
+pub trait TryCheckedIterator<T: Integer>: Iterator<Item = Result<T>> + Sized {
+    /// Sum all values, `what` describing the computation in the overflow error.
+    fn try_checked_sum(mut self, what: &str) -> Result<T> {
+        self.try_fold(T::ZERO, |sum, value| checked_add(sum, value?, what))
+    }
+}
+
+impl<T: Integer, I: Iterator<Item = Result<T>>> TryCheckedIterator<T> for I {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checked_sum_and_product() -> Result<()> {
+        assert_eq!([1u8, 2, 3].into_iter().checked_sum("sum")?, 6);
+        assert_eq!([2u8, 3, 4].into_iter().checked_product("product")?, 24);
+        assert_eq!(std::iter::empty::<u8>().checked_sum("sum")?, 0);
+        assert_eq!(std::iter::empty::<u8>().checked_product("product")?, 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn try_checked_sum() -> Result<()> {
+        assert_eq!([Ok(1u8), Ok(2)].into_iter().try_checked_sum("sum")?, 3);
+        assert!(matches!(
+            [Ok(1u8), Err(Error::GenericError), Ok(2)]
+                .into_iter()
+                .try_checked_sum("sum"),
+            Err(Error::GenericError)
+        ));
+        assert!(matches!(
+            [Ok(200u8), Ok(100)].into_iter().try_checked_sum("sum"),
+            Err(Error::OverflowError(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn overflow_is_an_error() {
+        assert!(matches!(
+            [200u8, 100].into_iter().checked_sum("sum"),
+            Err(Error::OverflowError(_))
+        ));
+        assert!(matches!(
+            [16u8, 16].into_iter().checked_product("product"),
+            Err(Error::OverflowError(_))
+        ));
+        assert!(matches!(
+            checked_add(Answer::MAX, 1, "sum"),
+            Err(Error::OverflowError(_))
+        ));
+        assert!(matches!(
+            checked_mul(Answer::MAX, 2, "product"),
+            Err(Error::OverflowError(_))
+        ));
+    }
+}
@@ -6,6 +6,7 @@ pub type Result<T> = std::result::Result<T, Error>;
 pub enum Error {
     GenericError,
     IoError(std::io::Error),
+    OverflowError(String),
     ParseError(String),
     ParseIntError(std::num::ParseIntError),
 }
@@ -15,6 +16,7 @@ impl fmt::Display for Error {
         match self {
             Self::GenericError => write!(f, "Generic error"),
             Self::IoError(err) => err.fmt(f),
+            Self::OverflowError(err) => write!(f, "Overflow error: {err}"),
             Self::ParseError(err) => write!(f, "Parse error: {err}"),
             Self::ParseIntError(err) => err.fmt(f),
         }
@@ -26,6 +28,7 @@ impl std::error::Error for Error {
         match self {
             Self::GenericError => None,
             Self::IoError(err) => Some(err),
+            Self::OverflowError(_) => None,
             Self::ParseError(_) => None,
             Self::ParseIntError(err) => Some(err),
         }
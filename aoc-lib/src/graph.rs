@@ -0,0 +1,407 @@
+//! Generic graph searches.
+//!
+//! Graphs are never stored: they are described by a start node and a
+//! neighbour function returning the nodes reachable from a given node,
+//! so they work equally with [`Grid`](crate::grid::Grid) positions or any
+//! other hashable node type. Visited nodes are tracked, so cyclic graphs
+//! are fine.
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+use crate::math::Integer;
+
+/// Rebuild the path leading to `goal` by following the `parents` links.
+fn reconstruct_path<N: Clone + Eq + Hash>(parents: &HashMap<N, N>, goal: N) -> Vec<N> {
+    let mut path = vec![goal];
+    while let Some(parent) = parents.get(path.last().expect("path is never empty")) {
+        path.push(parent.clone());
+    }
+    path.reverse();
+    path
+}
+
+/// Find a shortest path (in number of edges) from `start` to a node
+/// satisfying `is_goal`, using a breadth-first search.
+///
+/// The returned path includes both `start` and the goal node.
+pub fn bfs<N, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut parents = HashMap::new();
+    let mut visited = HashSet::from([start.clone()]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        if is_goal(&node) {
+            return Some(reconstruct_path(&parents, node));
+        }
+        for next in neighbours(&node) {
+            if visited.insert(next.clone()) {
+                parents.insert(next.clone(), node.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+/// Compute the distance (in number of edges) from `start` to every reachable node.
+///
+/// This is a flood fill: the keys of the returned map are the reachable nodes.
+pub fn bfs_distances<N, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> HashMap<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([(start, 0)]);
+
+    while let Some((node, distance)) = queue.pop_front() {
+        for next in neighbours(&node) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), distance + 1);
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+
+    distances
+}
+
+/// Find a path from `start` to a node satisfying `is_goal`, using a depth-first search.
+///
+/// The path is not necessarily the shortest one. It includes both `start` and the goal node.
+pub fn dfs<N, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut parents = HashMap::new();
+    let mut visited = HashSet::new();
+    let mut stack = vec![(start, None)];
+
+    while let Some((node, parent)) = stack.pop() {
+        if !visited.insert(node.clone()) {
+            continue;
+        }
+        if let Some(parent) = parent {
+            parents.insert(node.clone(), parent);
+        }
+        if is_goal(&node) {
+            return Some(reconstruct_path(&parents, node));
+        }
+        for next in neighbours(&node) {
+            if !visited.contains(&next) {
+                stack.push((next, Some(node.clone())));
+            }
+        }
+    }
+
+    None
+}
+
+/// List all nodes reachable from `start`, `start` included, in depth-first order.
+pub fn dfs_reachable<N, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> Vec<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut visited = HashSet::new();
+    let mut order = Vec::new();
+    let mut stack = vec![start];
+
+    while let Some(node) = stack.pop() {
+        if !visited.insert(node.clone()) {
+            continue;
+        }
+        for next in neighbours(&node) {
+            if !visited.contains(&next) {
+                stack.push(next);
+            }
+        }
+        order.push(node);
+    }
+
+    order
+}
+
+/// Find a cheapest path from `start` to a node satisfying `is_goal`, using Dijkstra's algorithm.
+///
+/// `neighbours` returns the reachable nodes along with the (non-negative) cost to reach them.
+/// Returns the path, including both `start` and the goal node, and its total cost.
+pub fn dijkstra<N, C, I>(
+    start: N,
+    neighbours: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Ord + Hash,
+    C: Integer,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, neighbours, |_| C::ZERO, is_goal)
+}
+
+/// Compute the cost of the cheapest path from `start` to every reachable node.
+pub fn dijkstra_all<N, C, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> HashMap<N, C>
+where
+    N: Clone + Ord + Hash,
+    C: Integer,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut costs = HashMap::from([(start.clone(), C::ZERO)]);
+    let mut heap = BinaryHeap::from([Reverse((C::ZERO, start))]);
+
+    while let Some(Reverse((cost, node))) = heap.pop() {
+        // Skip outdated heap entries.
+        if costs.get(&node).is_some_and(|best| *best < cost) {
+            continue;
+        }
+        for (next, edge_cost) in neighbours(&node) {
+            let next_cost = cost + edge_cost;
+            if costs.get(&next).is_none_or(|best| next_cost < *best) {
+                costs.insert(next.clone(), next_cost);
+                heap.push(Reverse((next_cost, next)));
+            }
+        }
+    }
+
+    costs
+}
+
+/// Find a cheapest path from `start` to a node satisfying `is_goal`, using the A* algorithm.
+///
+/// `heuristic` estimates the remaining cost to a goal. Nodes are never expanded twice,
+/// so for the returned path to be the cheapest one the heuristic must be consistent:
+/// it never decreases by more than the cost of an edge, `h(a) <= cost(a, b) + h(b)`,
+/// and is zero on goals. Being admissible (never overestimating) is not enough.
+/// Returns the path, including both `start` and the goal node, and its total cost.
+pub fn astar<N, C, I>(
+    start: N,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Ord + Hash,
+    C: Integer,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut parents = HashMap::new();
+    let mut costs = HashMap::from([(start.clone(), C::ZERO)]);
+    let mut closed = HashSet::new();
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), C::ZERO, start))]);
+
+    while let Some(Reverse((_, cost, node))) = heap.pop() {
+        if !closed.insert(node.clone()) {
+            continue;
+        }
+        if is_goal(&node) {
+            return Some((reconstruct_path(&parents, node), cost));
+        }
+        for (next, edge_cost) in neighbours(&node) {
+            if closed.contains(&next) {
+                continue;
+            }
+            let next_cost = cost + edge_cost;
+            if costs.get(&next).is_none_or(|best| next_cost < *best) {
+                costs.insert(next.clone(), next_cost);
+                parents.insert(next.clone(), node.clone());
+                heap.push(Reverse((next_cost + heuristic(&next), next_cost, next)));
+            }
+        }
+    }
+
+    None
+}
+
+/// Group `nodes` into connected components.
+///
+/// `neighbours` must be symmetric for the result to be meaningful. Components are
+/// returned in the order of their first node in `nodes`, each in depth-first order.
+/// Nodes reachable through `neighbours` but absent from `nodes` are included in
+/// the component that reaches them.
+pub fn connected_components<N, I>(
+    nodes: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+) -> Vec<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut visited = HashSet::new();
+    let mut components = Vec::new();
+
+    for node in nodes {
+        if visited.contains(&node) {
+            continue;
+        }
+        let component = dfs_reachable(node, |n| {
+            neighbours(n)
+                .into_iter()
+                .filter(|next| !visited.contains(next))
+                .collect::<Vec<_>>()
+        });
+        visited.extend(component.iter().cloned());
+        components.push(component);
+    }
+
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::{Grid, Position};
+    use crate::Result;
+
+    /// A small maze, with walls as `#`.
+    fn maze() -> Result<Grid<bool>> {
+        Grid::parse(
+            "\
+..#....
+.##.##.
+...#...
+.#...#.",
+            |c| Ok(c == '#'),
+        )
+    }
+
+    fn open_neighbours(grid: &Grid<bool>, position: Position) -> Vec<Position> {
+        grid.orthogonal_neighbours(position)
+            .filter(|next| !grid[*next])
+            .collect()
+    }
+
+    #[test]
+    fn bfs_shortest_path() -> Result<()> {
+        let grid = maze()?;
+        let path = bfs((0, 0), |p| open_neighbours(&grid, *p), |p| *p == (0, 6));
+
+        let path = path.expect("goal is reachable");
+        assert_eq!(path.first(), Some(&(0, 0)));
+        assert_eq!(path.last(), Some(&(0, 6)));
+        assert_eq!(path.len(), 13);
+
+        Ok(())
+    }
+
+    #[test]
+    fn bfs_unreachable() -> Result<()> {
+        let grid = maze()?;
+
+        assert_eq!(
+            bfs((0, 0), |p| open_neighbours(&grid, *p), |p| *p == (0, 2)),
+            None
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn bfs_flood_fill() -> Result<()> {
+        let grid = maze()?;
+        let distances = bfs_distances((0, 0), |p| open_neighbours(&grid, *p));
+
+        assert_eq!(distances[&(0, 0)], 0);
+        assert_eq!(distances[&(2, 2)], 4);
+        assert_eq!(distances[&(0, 6)], 12);
+        assert!(!distances.contains_key(&(0, 2)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn dfs_on_cycle() {
+        // 0 -> 1 -> 2 -> 0, 2 -> 3
+        let edges = |n: &u32| match n {
+            0 => vec![1],
+            1 => vec![2],
+            2 => vec![0, 3],
+            _ => vec![],
+        };
+
+        assert_eq!(dfs(0, edges, |n| *n == 3), Some(vec![0, 1, 2, 3]));
+        assert_eq!(dfs(0, edges, |n| *n == 4), None);
+        assert_eq!(dfs_reachable(1, edges), vec![1, 2, 3, 0]);
+    }
+
+    #[test]
+    fn dijkstra_weighted() {
+        let edges = |n: &char| match n {
+            'a' => vec![('b', 7), ('c', 9), ('f', 14)],
+            'b' => vec![('a', 7), ('c', 10), ('d', 15)],
+            'c' => vec![('a', 9), ('b', 10), ('d', 11), ('f', 2)],
+            'd' => vec![('b', 15), ('c', 11), ('e', 6)],
+            'e' => vec![('d', 6), ('f', 9)],
+            'f' => vec![('a', 14), ('c', 2), ('e', 9)],
+            _ => vec![],
+        };
+
+        assert_eq!(
+            dijkstra('a', edges, |n| *n == 'e'),
+            Some((vec!['a', 'c', 'f', 'e'], 20u32))
+        );
+
+        let costs = dijkstra_all('a', edges);
+        assert_eq!(costs[&'d'], 20u32);
+        assert_eq!(costs[&'f'], 11);
+        assert_eq!(costs.len(), 6);
+    }
+
+    #[test]
+    fn astar_on_grid() -> Result<()> {
+        let grid = maze()?;
+        let goal: Position = (3, 6);
+        let manhattan =
+            |(line, column): &Position| goal.0.abs_diff(*line) + goal.1.abs_diff(*column);
+
+        let (path, cost) = astar(
+            (0, 0),
+            |p| open_neighbours(&grid, *p).into_iter().map(|next| (next, 1)),
+            manhattan,
+            |p| *p == goal,
+        )
+        .expect("goal is reachable");
+
+        assert_eq!(cost, 11);
+        assert_eq!(path.len(), 12);
+        assert_eq!(
+            bfs((0, 0), |p| open_neighbours(&grid, *p), |p| *p == goal).map(|p| p.len()),
+            Some(12)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn grid_connected_components() -> Result<()> {
+        let grid = maze()?;
+        let walls: Vec<Position> = grid.iter().filter(|(_, w)| **w).map(|(p, _)| p).collect();
+
+        let components = connected_components(walls, |p| {
+            grid.orthogonal_neighbours(*p)
+                .filter(|next| grid[*next])
+                .collect::<Vec<_>>()
+        });
+
+        assert_eq!(
+            components.iter().map(Vec::len).collect::<Vec<_>>(),
+            vec![3, 2, 1, 1, 1]
+        );
+
+        Ok(())
+    }
+}
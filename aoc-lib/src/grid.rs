@@ -0,0 +1,195 @@
+//! Two-dimension grid of cells, addressed by `(line, column)` positions.
+use std::ops::{Index, IndexMut};
+
+use crate::{Error, Result};
+
+/// Position in a grid: (line_number, column_number).
+pub type Position = (usize, usize);
+
+/// Offsets of the four orthogonal neighbours: up, left, right, down.
+const ORTHOGONAL_OFFSETS: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+/// Offsets of the eight neighbours, in reading order.
+const ALL_OFFSETS: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// Rectangular grid storing its cells line by line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Create a grid from its cells, given line by line.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Result<Self> {
+        if cells.len() != width * height {
+            return Err(Error::ParseError(format!(
+                "{} cells cannot fill a {width}x{height} grid",
+                cells.len()
+            )));
+        }
+
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /// Parse a grid from text, converting each character with `parse_cell`.
+    ///
+    /// All lines must have the same number of characters.
+    pub fn parse(input: &str, mut parse_cell: impl FnMut(char) -> Result<T>) -> Result<Self> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+
+        for (line_number, line) in input.lines().enumerate() {
+            let len_before = cells.len();
+            for c in line.chars() {
+                cells.push(parse_cell(c)?);
+            }
+            let line_width = cells.len() - len_before;
+
+            match width {
+                None => width = Some(line_width),
+                Some(width) if width != line_width => {
+                    return Err(Error::ParseError(format!(
+                        "line {line_number} has {line_width} cells, expected {width}"
+                    )))
+                }
+                Some(_) => (),
+            }
+            height += 1;
+        }
+
+        Self::new(width.unwrap_or(0), height, cells)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Check if the position is inside the grid.
+    pub fn contains(&self, (line, column): Position) -> bool {
+        line < self.height && column < self.width
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.contains(position)
+            .then(|| &self.cells[position.0 * self.width + position.1])
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        self.contains(position)
+            .then(|| &mut self.cells[position.0 * self.width + position.1])
+    }
+
+    /// Iterate over all positions, in reading order.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.height).flat_map(move |line| (0..width).map(move |column| (line, column)))
+    }
+
+    /// Iterate over all cells with their position, in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Iterate over the up to four orthogonal neighbours of `position` inside the grid.
+    pub fn orthogonal_neighbours(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        self.offset_positions(position, &ORTHOGONAL_OFFSETS)
+    }
+
+    /// Iterate over the up to eight neighbours of `position` inside the grid,
+    /// diagonals included.
+    pub fn neighbours(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        self.offset_positions(position, &ALL_OFFSETS)
+    }
+
+    fn offset_positions(
+        &self,
+        (line, column): Position,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = Position> + '_ {
+        offsets.iter().filter_map(move |(d_line, d_column)| {
+            let position = (
+                line.checked_add_signed(*d_line)?,
+                column.checked_add_signed(*d_column)?,
+            );
+            self.contains(position).then_some(position)
+        })
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &Self::Output {
+        self.get(position)
+            .unwrap_or_else(|| panic!("{position:?} is outside the grid"))
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut Self::Output {
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("{position:?} is outside the grid"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Result<Grid<char>> {
+        Grid::parse("abc\ndef", Ok)
+    }
+
+    #[test]
+    fn parse_grid() -> Result<()> {
+        let grid = sample()?;
+
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid[(1, 2)], 'f');
+        assert_eq!(grid.get((2, 0)), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_ragged_grid() {
+        assert!(Grid::parse("abc\nde", Ok).is_err());
+    }
+
+    #[test]
+    fn grid_neighbours() -> Result<()> {
+        let grid = sample()?;
+
+        assert_eq!(
+            grid.orthogonal_neighbours((0, 0)).collect::<Vec<_>>(),
+            vec![(0, 1), (1, 0)]
+        );
+        assert_eq!(
+            grid.neighbours((1, 1)).collect::<Vec<_>>(),
+            vec![(0, 0), (0, 1), (0, 2), (1, 0), (1, 2)]
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,395 @@
+//! Integer intervals and sets of intervals.
+use std::ops::RangeInclusive;
+
+use crate::math::Integer;
+
+/// Non-empty interval of integers, stored with inclusive bounds.
+///
+/// Intervals can be built from inclusive bounds, exclusive end bounds or a
+/// start and a length; constructors return `None` for empty intervals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval<T> {
+    start: T,
+    end: T,
+}
+
+impl<T: Integer> Interval<T> {
+    /// Interval containing a single value.
+    pub fn point(value: T) -> Self {
+        Self {
+            start: value,
+            end: value,
+        }
+    }
+
+    /// Interval going from `start` to `end`, both included.
+    pub fn inclusive(start: T, end: T) -> Option<Self> {
+        (start <= end).then_some(Self { start, end })
+    }
+
+    /// Interval going from `start` included to `end` excluded.
+    pub fn exclusive(start: T, end: T) -> Option<Self> {
+        (start < end).then(|| Self {
+            start,
+            end: end - T::ONE,
+        })
+    }
+
+    /// Interval of `len` values, starting at `start`.
+    ///
+    /// Returns `None` if `len` is not strictly positive or the interval goes past `T::MAX`.
+    pub fn from_len(start: T, len: T) -> Option<Self> {
+        Self::exclusive(start, start.checked_add(len)?)
+    }
+
+    /// First value of the interval.
+    pub fn start(&self) -> T {
+        self.start
+    }
+
+    /// Last value of the interval.
+    pub fn end(&self) -> T {
+        self.end
+    }
+
+    /// First value after the interval, or `None` if the interval ends at `T::MAX`.
+    pub fn end_exclusive(&self) -> Option<T> {
+        self.end.checked_add(T::ONE)
+    }
+
+    /// Number of values in the interval, or `None` if it does not fit in `T`.
+    pub fn len(&self) -> Option<T> {
+        self.end.checked_sub(self.start)?.checked_add(T::ONE)
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value <= self.end
+    }
+
+    /// Check if `other` is entirely inside this interval.
+    pub fn contains_interval(&self, other: &Self) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    /// Check if both intervals have at least one value in common.
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    /// Check if both intervals overlap or are directly next to each other,
+    /// so that their union is an interval.
+    pub fn touches(&self, other: &Self) -> bool {
+        self.start <= other.end.saturating_add(T::ONE)
+            && other.start <= self.end.saturating_add(T::ONE)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        Self::inclusive(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    /// Union of both intervals, or `None` if they do not touch.
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        self.touches(other).then(|| self.hull(other))
+    }
+
+    /// Smallest interval containing both intervals.
+    pub fn hull(&self, other: &Self) -> Self {
+        Self {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        }
+    }
+
+    /// Values of this interval that are not in `other`, as the parts lying
+    /// before and after `other`.
+    pub fn difference(&self, other: &Self) -> (Option<Self>, Option<Self>) {
+        if !self.overlaps(other) {
+            return if self.end < other.start {
+                (Some(*self), None)
+            } else {
+                (None, Some(*self))
+            };
+        }
+
+        (
+            Self::exclusive(self.start, other.start),
+            (other.end < self.end).then(|| Self {
+                start: other.end + T::ONE,
+                end: self.end,
+            }),
+        )
+    }
+
+    /// Split the interval into the values lower than `value` and the ones
+    /// greater or equal to it.
+    pub fn split_at(&self, value: T) -> (Option<Self>, Option<Self>) {
+        (
+            Self::exclusive(self.start, value.min(self.end.saturating_add(T::ONE)))
+                .filter(|_| value > self.start),
+            Self::inclusive(value.max(self.start), self.end),
+        )
+    }
+
+    /// Grow the interval by `amount` on both sides, saturating at the bounds of `T`.
+    pub fn expand(&self, amount: T) -> Self {
+        Self {
+            start: self.start.saturating_sub(amount),
+            end: self.end.saturating_add(amount),
+        }
+    }
+}
+
+impl<T: Integer> From<Interval<T>> for RangeInclusive<T> {
+    fn from(value: Interval<T>) -> Self {
+        value.start..=value.end
+    }
+}
+
+/// Set of integers stored as sorted, disjoint and non-touching intervals.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self {
+            intervals: Vec::new(),
+        }
+    }
+}
+
+impl<T: Integer> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Number of values in the set, or `None` if it does not fit in `T`.
+    pub fn len(&self) -> Option<T> {
+        self.intervals
+            .iter()
+            .try_fold(T::ZERO, |len, interval| len.checked_add(interval.len()?))
+    }
+
+    /// Iterate over the merged intervals of the set, in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = &Interval<T>> {
+        self.intervals.iter()
+    }
+
+    /// Add all values of `interval` to the set, merging it with the intervals it touches.
+    pub fn insert(&mut self, interval: Interval<T>) {
+        let first = self
+            .intervals
+            .partition_point(|i| i.end.saturating_add(T::ONE) < interval.start);
+        let last = self
+            .intervals
+            .partition_point(|i| i.start <= interval.end.saturating_add(T::ONE));
+
+        let merged = self.intervals[first..last]
+            .iter()
+            .fold(interval, |merged, i| merged.hull(i));
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    /// Remove all values of `interval` from the set, splitting intervals if needed.
+    pub fn remove(&mut self, interval: &Interval<T>) {
+        let first = self.intervals.partition_point(|i| i.end < interval.start);
+        let last = self.intervals.partition_point(|i| i.start <= interval.end);
+
+        let remaining: Vec<_> = self.intervals[first..last]
+            .iter()
+            .flat_map(|i| {
+                let (before, after) = i.difference(interval);
+                before.into_iter().chain(after)
+            })
+            .collect();
+        self.intervals.splice(first..last, remaining);
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let index = self.intervals.partition_point(|i| i.end < value);
+        self.intervals
+            .get(index)
+            .is_some_and(|interval| interval.contains(value))
+    }
+
+    /// Check if all values of `interval` are in the set.
+    pub fn contains_interval(&self, interval: &Interval<T>) -> bool {
+        let index = self.intervals.partition_point(|i| i.end < interval.start);
+        self.intervals
+            .get(index)
+            .is_some_and(|i| i.contains_interval(interval))
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        other.iter().for_each(|interval| union.insert(*interval));
+        union
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+        let (mut i, mut j) = (0, 0);
+
+        while let (Some(a), Some(b)) = (self.intervals.get(i), other.intervals.get(j)) {
+            intervals.extend(a.intersection(b));
+            // Move forward on the interval that ends first.
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        Self { intervals }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut difference = self.clone();
+        other
+            .iter()
+            .for_each(|interval| difference.remove(interval));
+        difference
+    }
+}
+
+impl<T: Integer> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        iter.into_iter().for_each(|interval| set.insert(interval));
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interval(start: i32, end: i32) -> Interval<i32> {
+        Interval::inclusive(start, end).expect("interval should not be empty")
+    }
+
+    #[test]
+    fn interval_bounds() {
+        assert_eq!(Interval::exclusive(2u32, 5), Interval::inclusive(2, 4));
+        assert_eq!(Interval::from_len(2u32, 3), Interval::inclusive(2, 4));
+        assert_eq!(Interval::exclusive(2u32, 2), None);
+        assert_eq!(Interval::inclusive(3u32, 2), None);
+        assert_eq!(Interval::from_len(u8::MAX, 2), None);
+        assert_eq!(
+            Interval::inclusive(0u8, u8::MAX).and_then(|i| i.len()),
+            None
+        );
+        assert_eq!(interval(-2, 2).len(), Some(5));
+        assert_eq!(interval(-2, 2).end_exclusive(), Some(3));
+    }
+
+    #[test]
+    fn interval_queries() {
+        let i = interval(2, 6);
+
+        assert!(i.contains(2) && i.contains(6) && !i.contains(7));
+        assert!(i.contains_interval(&interval(3, 6)));
+        assert!(!i.contains_interval(&interval(3, 7)));
+        assert!(i.overlaps(&interval(6, 9)));
+        assert!(!i.overlaps(&interval(7, 9)));
+        assert!(i.touches(&interval(7, 9)));
+        assert!(!i.touches(&interval(8, 9)));
+    }
+
+    #[test]
+    fn interval_operations() {
+        let i = interval(2, 6);
+
+        assert_eq!(i.intersection(&interval(4, 9)), Some(interval(4, 6)));
+        assert_eq!(i.intersection(&interval(7, 9)), None);
+        assert_eq!(i.union(&interval(7, 9)), Some(interval(2, 9)));
+        assert_eq!(i.union(&interval(8, 9)), None);
+        assert_eq!(i.hull(&interval(8, 9)), interval(2, 9));
+        assert_eq!(
+            i.difference(&interval(3, 4)),
+            (Some(interval(2, 2)), Some(interval(5, 6)))
+        );
+        assert_eq!(i.difference(&interval(0, 9)), (None, None));
+        assert_eq!(i.difference(&interval(8, 9)), (Some(i), None));
+        assert_eq!(i.difference(&interval(-3, 0)), (None, Some(i)));
+    }
+
+    #[test]
+    fn interval_split_and_expand() {
+        let i = interval(2, 6);
+
+        assert_eq!(i.split_at(4), (Some(interval(2, 3)), Some(interval(4, 6))));
+        assert_eq!(i.split_at(2), (None, Some(i)));
+        assert_eq!(i.split_at(7), (Some(i), None));
+        assert_eq!(i.split_at(-10), (None, Some(i)));
+        assert_eq!(
+            Interval::from_len(0usize, 3).map(|i| i.expand(1)),
+            Interval::inclusive(0, 3)
+        );
+        assert_eq!(i.expand(2), interval(0, 8));
+    }
+
+    #[test]
+    fn interval_set_merging() {
+        let set: IntervalSet<i32> = [
+            interval(5, 7),
+            interval(1, 2),
+            interval(3, 3),
+            interval(10, 12),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            set.iter().copied().collect::<Vec<_>>(),
+            vec![interval(1, 3), interval(5, 7), interval(10, 12)]
+        );
+        assert_eq!(set.len(), Some(9));
+
+        let mut set = set;
+        set.insert(interval(4, 9));
+        assert_eq!(
+            set.iter().copied().collect::<Vec<_>>(),
+            vec![interval(1, 12)]
+        );
+    }
+
+    #[test]
+    fn interval_set_removal() {
+        let mut set: IntervalSet<i32> = [interval(1, 10), interval(20, 30)].into_iter().collect();
+        set.remove(&interval(5, 22));
+
+        assert_eq!(
+            set.iter().copied().collect::<Vec<_>>(),
+            vec![interval(1, 4), interval(23, 30)]
+        );
+        assert!(set.contains(4) && !set.contains(5) && set.contains(23));
+        assert!(set.contains_interval(&interval(24, 30)));
+        assert!(!set.contains_interval(&interval(3, 23)));
+    }
+
+    #[test]
+    fn interval_set_operations() {
+        let a: IntervalSet<i32> = [interval(0, 5), interval(10, 15)].into_iter().collect();
+        let b: IntervalSet<i32> = [interval(3, 12)].into_iter().collect();
+
+        assert_eq!(
+            a.union(&b).iter().copied().collect::<Vec<_>>(),
+            vec![interval(0, 15)]
+        );
+        assert_eq!(
+            a.intersection(&b).iter().copied().collect::<Vec<_>>(),
+            vec![interval(3, 5), interval(10, 12)]
+        );
+        assert_eq!(
+            a.difference(&b).iter().copied().collect::<Vec<_>>(),
+            vec![interval(0, 2), interval(13, 15)]
+        );
+        assert!(a.difference(&a).is_empty());
+    }
+}
@@ -1,4 +1,11 @@
+pub mod answer;
+pub mod csv;
 pub mod error;
+pub mod graph;
+pub mod grid;
+pub mod interval;
+pub mod math;
+pub mod repl;
 
 pub use error::Error;
 pub use error::Result;
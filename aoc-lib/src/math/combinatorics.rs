@@ -0,0 +1,124 @@
+use super::{gcd, Integer};
+
+/// Factorial of `n`, or `None` if `n` is negative or the result overflows `T`.
+pub fn checked_factorial<T: Integer>(n: T) -> Option<T> {
+    if n.is_negative() {
+        return None;
+    }
+
+    let mut result = T::ONE;
+    let mut i = T::TWO;
+    while i <= n {
+        result = result.checked_mul(i)?;
+        i = i + T::ONE;
+    }
+    Some(result)
+}
+
+/// Factorial of `n`, wrapping around on overflow. Negative values yield `1`.
+pub fn wrapping_factorial<T: Integer>(n: T) -> T {
+    let mut result = T::ONE;
+    let mut i = T::TWO;
+    while i <= n {
+        result = result.wrapping_mul(i);
+        // `i` can reach `T::MAX` only if `n` is `T::MAX`.
+        match i.checked_add(T::ONE) {
+            Some(next) => i = next,
+            None => break,
+        }
+    }
+    result
+}
+
+/// Factorial of `n`.
+///
+/// # Panics
+///
+/// Panics if `n` is negative or the result overflows `T`.
+pub fn factorial<T: Integer>(n: T) -> T {
+    checked_factorial(n).expect("factorial overflowed")
+}
+
+/// Binomial coefficient "`n` choose `k`", or `None` if `n` is negative or the
+/// result overflows `T`.
+///
+/// Intermediate values never exceed the result, so this only fails when the
+/// coefficient itself does not fit. Returns `Some(0)` when `k` is negative or
+/// greater than `n`.
+pub fn checked_binomial<T: Integer>(n: T, k: T) -> Option<T> {
+    if n.is_negative() {
+        return None;
+    }
+    if k.is_negative() || k > n {
+        return Some(T::ZERO);
+    }
+
+    // C(n, k) == C(n, n - k), take the smaller one to do fewer iterations.
+    let k = if k > n - k { n - k } else { k };
+
+    let mut result = T::ONE;
+    let mut i = T::ZERO;
+    while i < k {
+        // result * (n - i) is divisible by (i + 1). Dividing by the gcd first
+        // ensures (i + 1) / g divides (n - i), so nothing overflows needlessly.
+        let divisor = i + T::ONE;
+        let g = gcd(result, divisor);
+        result = (result / g).checked_mul((n - i) / (divisor / g))?;
+        i = divisor;
+    }
+    Some(result)
+}
+
+/// Binomial coefficient "`n` choose `k`".
+///
+/// # Panics
+///
+/// Panics if `n` is negative or the result overflows `T`.
+pub fn binomial<T: Integer>(n: T, k: T) -> T {
+    checked_binomial(n, k).expect("binomial overflowed")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn factorial_values() {
+        assert_eq!(factorial(0u32), 1);
+        assert_eq!(factorial(1u32), 1);
+        assert_eq!(factorial(5u32), 120);
+        assert_eq!(factorial(20u64), 2_432_902_008_176_640_000);
+        assert_eq!(checked_factorial(-1i32), None);
+    }
+
+    #[test]
+    fn factorial_overflow() {
+        assert_eq!(checked_factorial(5u8), Some(120));
+        assert_eq!(checked_factorial(6u8), None);
+        assert_eq!(wrapping_factorial(6u8), 208);
+        assert_eq!(checked_factorial(21u64), None);
+        assert_eq!(wrapping_factorial(u8::MAX), 0);
+    }
+
+    #[test]
+    fn binomial_values() {
+        assert_eq!(binomial(5u32, 2), 10);
+        assert_eq!(binomial(5u32, 0), 1);
+        assert_eq!(binomial(5u32, 5), 1);
+        assert_eq!(binomial(5u32, 6), 0);
+        assert_eq!(binomial(5i32, -1), 0);
+        assert_eq!(checked_binomial(-5i32, 2), None);
+        assert_eq!(binomial(52u64, 5), 2_598_960);
+    }
+
+    #[test]
+    fn binomial_no_spurious_overflow() {
+        // 10 choose 5 fits in a u8 even though 10 * 9 * 8 does not.
+        assert_eq!(checked_binomial(10u8, 5), Some(252));
+        assert_eq!(
+            checked_binomial(67u64, 33),
+            Some(14_226_520_737_620_288_370)
+        );
+        assert_eq!(checked_binomial(68u64, 34), None);
+    }
+}
@@ -0,0 +1,13 @@
+//! Number-theory and combinatorics helpers.
+//!
+//! All functions are generic over [`Integer`], which is implemented for every
+//! primitive integer type. Operations that can overflow come in three flavours:
+//! `checked_*` returns `None` on overflow, `wrapping_*` wraps around at the
+//! type's boundary and the plain version panics.
+mod combinatorics;
+mod integer;
+mod number_theory;
+
+pub use combinatorics::*;
+pub use integer::*;
+pub use number_theory::*;
@@ -0,0 +1,308 @@
+use super::Integer;
+
+/// Greatest common divisor of `a` and `b`, always non-negative.
+///
+/// `gcd(0, 0)` is `0`. For signed types, the result wraps if it is `T::MIN`'s
+/// absolute value, e.g. `gcd(i32::MIN, 0)`.
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    let (mut a, mut b) = (a, b);
+    while b != T::ZERO {
+        (a, b) = (b, a.wrapping_rem(b));
+    }
+    a.wrapping_abs()
+}
+
+/// Least common multiple of `a` and `b`, or `None` if it overflows `T`.
+pub fn checked_lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+    let lcm = (a / gcd(a, b)).checked_mul(b)?;
+    if lcm.is_negative() {
+        T::ZERO.checked_sub(lcm)
+    } else {
+        Some(lcm)
+    }
+}
+
+/// Least common multiple of `a` and `b`, wrapping around on overflow.
+pub fn wrapping_lcm<T: Integer>(a: T, b: T) -> T {
+    if a == T::ZERO || b == T::ZERO {
+        return T::ZERO;
+    }
+    (a / gcd(a, b)).wrapping_mul(b).wrapping_abs()
+}
+
+/// Least common multiple of `a` and `b`.
+///
+/// # Panics
+///
+/// Panics if the result overflows `T`.
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    checked_lcm(a, b).expect("lcm overflowed")
+}
+
+/// Least common multiple of all values, or `None` if it overflows `T`.
+///
+/// The lcm of an empty iterator is `1`.
+pub fn checked_lcm_of<T: Integer>(values: impl IntoIterator<Item = T>) -> Option<T> {
+    values
+        .into_iter()
+        .try_fold(T::ONE, |acc, value| checked_lcm(acc, value))
+}
+
+/// Computes `(a + b) mod m` for `a` and `b` in `0..m` without overflowing.
+fn add_mod<T: Integer>(a: T, b: T, m: T) -> T {
+    if a >= m - b {
+        a - (m - b)
+    } else {
+        a + b
+    }
+}
+
+/// Computes `(a - b) mod m` for `a` and `b` in `0..m` without overflowing.
+fn sub_mod<T: Integer>(a: T, b: T, m: T) -> T {
+    if a >= b {
+        a - b
+    } else {
+        a + (m - b)
+    }
+}
+
+/// Computes `(a * b) mod m` for `a` and `b` in `0..m` without overflowing,
+/// using double-and-add.
+fn mul_mod<T: Integer>(a: T, b: T, m: T) -> T {
+    let (mut a, mut b) = (a, b);
+    let mut result = T::ZERO;
+    while b != T::ZERO {
+        if b % T::TWO == T::ONE {
+            result = add_mod(result, a, m);
+        }
+        a = add_mod(a, a, m);
+        b = b / T::TWO;
+    }
+    result
+}
+
+/// Computes `base.pow(exp) mod modulus`, with the result in `0..modulus`.
+///
+/// Intermediate products never overflow, even when `modulus` is close to `T::MAX`.
+/// Returns `None` if `modulus` is not strictly positive or `exp` is negative.
+pub fn checked_mod_pow<T: Integer>(base: T, exp: T, modulus: T) -> Option<T> {
+    if modulus <= T::ZERO || exp.is_negative() {
+        return None;
+    }
+
+    let mut base = base.rem_euclid(modulus);
+    let mut exp = exp;
+    let mut result = T::ONE % modulus;
+    while exp != T::ZERO {
+        if exp % T::TWO == T::ONE {
+            result = mul_mod(result, base, modulus);
+        }
+        base = mul_mod(base, base, modulus);
+        exp = exp / T::TWO;
+    }
+    Some(result)
+}
+
+/// Computes `base.pow(exp) mod modulus`, with the result in `0..modulus`.
+///
+/// # Panics
+///
+/// Panics if `modulus` is not strictly positive or `exp` is negative.
+pub fn mod_pow<T: Integer>(base: T, exp: T, modulus: T) -> T {
+    checked_mod_pow(base, exp, modulus).expect("mod_pow needs a positive modulus and exponent")
+}
+
+/// Modular multiplicative inverse of `a` modulo `modulus`, in `0..modulus`.
+///
+/// Returns `None` if `modulus` is not strictly positive or if `a` and
+/// `modulus` are not coprime.
+pub fn mod_inverse<T: Integer>(a: T, modulus: T) -> Option<T> {
+    if modulus <= T::ZERO {
+        return None;
+    }
+
+    // Extended Euclidean algorithm, keeping the Bézout coefficient reduced
+    // modulo `modulus` so that it works with unsigned types.
+    let (mut old_r, mut r) = (a.rem_euclid(modulus), modulus);
+    let (mut old_t, mut t) = (T::ONE % modulus, T::ZERO);
+    while r != T::ZERO {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_t, t) = (
+            t,
+            sub_mod(old_t, mul_mod(quotient % modulus, t, modulus), modulus),
+        );
+    }
+
+    (old_r == T::ONE).then_some(old_t)
+}
+
+/// Solves a system of congruences `x ≡ residue (mod modulus)` with the Chinese
+/// remainder theorem.
+///
+/// Moduli do not need to be pairwise coprime. Returns `(x, lcm)` where `x` is the
+/// smallest non-negative solution and `lcm` the least common multiple of all
+/// moduli, so that every solution is `x + k * lcm`. Returns `None` if the system
+/// has no solution, a modulus is not strictly positive or `lcm` overflows `T`.
+pub fn chinese_remainder<T: Integer>(
+    congruences: impl IntoIterator<Item = (T, T)>,
+) -> Option<(T, T)> {
+    congruences
+        .into_iter()
+        .try_fold((T::ZERO, T::ONE), |(r1, m1), (r2, m2)| {
+            if m2 <= T::ZERO {
+                return None;
+            }
+            let r2 = r2.rem_euclid(m2);
+
+            let g = gcd(m1, m2);
+            let diff = sub_mod(r2, r1 % m2, m2);
+            if diff % g != T::ZERO {
+                return None;
+            }
+
+            // x = r1 + m1 * k with m1 * k ≡ diff (mod m2).
+            let m2_g = m2 / g;
+            let k = mul_mod((diff / g) % m2_g, mod_inverse(m1 / g, m2_g)?, m2_g);
+            let lcm = (m1 / g).checked_mul(m2)?;
+            Some((r1 + m1 * k, lcm))
+        })
+}
+
+/// Floor of the square root of `n`, or `None` if `n` is negative.
+pub fn checked_isqrt<T: Integer>(n: T) -> Option<T> {
+    if n.is_negative() {
+        return None;
+    }
+    if n < T::TWO {
+        return Some(n);
+    }
+
+    // Newton's method, starting above the root so that the sequence decreases.
+    let mut x = n / T::TWO + T::ONE;
+    let mut y = (x + n / x) / T::TWO;
+    while y < x {
+        x = y;
+        y = (x + n / x) / T::TWO;
+    }
+    Some(x)
+}
+
+/// Floor of the square root of `n`.
+///
+/// # Panics
+///
+/// Panics if `n` is negative.
+pub fn isqrt<T: Integer>(n: T) -> T {
+    checked_isqrt(n).expect("isqrt of a negative number")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcd_values() {
+        assert_eq!(gcd(12u32, 18), 6);
+        assert_eq!(gcd(17u64, 5), 1);
+        assert_eq!(gcd(0u8, 7), 7);
+        assert_eq!(gcd(7u8, 0), 7);
+        assert_eq!(gcd(0u8, 0), 0);
+        assert_eq!(gcd(-12i32, 18), 6);
+        assert_eq!(gcd(12i32, -18), 6);
+        assert_eq!(gcd(-12i64, -18), 6);
+        assert_eq!(gcd(i32::MIN, -1), 1);
+        assert_eq!(gcd(-1, i32::MIN), 1);
+        assert_eq!(gcd(i8::MIN, i8::MIN), i8::MIN);
+    }
+
+    #[test]
+    fn lcm_values() {
+        assert_eq!(lcm(4u32, 6), 12);
+        assert_eq!(lcm(-4i32, 6), 12);
+        assert_eq!(lcm(0u32, 6), 0);
+        assert_eq!(checked_lcm_of([2u64, 3, 4, 5]), Some(60));
+        assert_eq!(checked_lcm_of(Vec::<u64>::new()), Some(1));
+    }
+
+    #[test]
+    fn lcm_overflow() {
+        assert_eq!(checked_lcm(200u8, 3), None);
+        assert_eq!(wrapping_lcm(200u8, 3), 200u8.wrapping_mul(3));
+        assert_eq!(checked_lcm(i8::MAX, 2), None);
+        assert_eq!(checked_lcm(128u8, 64), Some(128));
+        // The intermediate product would overflow, but the result fits.
+        assert_eq!(checked_lcm(u64::MAX, u64::MAX), Some(u64::MAX));
+    }
+
+    #[test]
+    #[should_panic]
+    fn lcm_panics_on_overflow() {
+        lcm(200u8, 3);
+    }
+
+    #[test]
+    fn mod_pow_values() {
+        assert_eq!(mod_pow(2u32, 10, 1000), 24);
+        assert_eq!(mod_pow(3u64, 0, 7), 1);
+        assert_eq!(mod_pow(3u64, 0, 1), 0);
+        assert_eq!(mod_pow(-2i32, 3, 5), 2);
+        assert_eq!(checked_mod_pow(2i32, -1, 5), None);
+        assert_eq!(checked_mod_pow(2u32, 1, 0), None);
+    }
+
+    #[test]
+    fn mod_pow_large_modulus() {
+        // Fermat's little theorem with a prime close to u64::MAX.
+        let p = 18_446_744_073_709_551_557u64;
+        assert_eq!(mod_pow(123_456_789, p - 1, p), 1);
+        assert_eq!(mod_pow(u8::MAX - 1, u8::MAX, u8::MAX), 254);
+    }
+
+    #[test]
+    fn mod_inverse_values() {
+        assert_eq!(mod_inverse(3u32, 11), Some(4));
+        assert_eq!(mod_inverse(10u32, 17), Some(12));
+        assert_eq!(mod_inverse(-3i32, 11), Some(7));
+        assert_eq!(mod_inverse(6u32, 9), None);
+        assert_eq!(mod_inverse(5u32, 1), Some(0));
+        assert_eq!(mod_inverse(5i32, -7), None);
+    }
+
+    #[test]
+    fn chinese_remainder_coprime() {
+        assert_eq!(
+            chinese_remainder([(2u64, 3), (3, 5), (2, 7)]),
+            Some((23, 105))
+        );
+        assert_eq!(chinese_remainder([(-1i64, 3), (-2, 5)]), Some((8, 15)));
+        assert_eq!(chinese_remainder(Vec::<(u32, u32)>::new()), Some((0, 1)));
+    }
+
+    #[test]
+    fn chinese_remainder_non_coprime() {
+        assert_eq!(chinese_remainder([(2u32, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(chinese_remainder([(1u32, 4), (2, 6)]), None);
+    }
+
+    #[test]
+    fn chinese_remainder_overflow() {
+        assert_eq!(chinese_remainder([(1u8, 16), (2, 17)]), None);
+        assert_eq!(chinese_remainder([(1u8, 0)]), None);
+    }
+
+    #[test]
+    fn isqrt_values() {
+        assert_eq!(isqrt(0u32), 0);
+        assert_eq!(isqrt(1u32), 1);
+        assert_eq!(isqrt(15u32), 3);
+        assert_eq!(isqrt(16u32), 4);
+        assert_eq!(isqrt(u8::MAX), 15);
+        assert_eq!(isqrt(u64::MAX), u32::MAX as u64);
+        assert_eq!(isqrt(i128::MAX), 13_043_817_825_332_782_212);
+        assert_eq!(checked_isqrt(-4i32), None);
+    }
+}
@@ -0,0 +1,173 @@
+//! Minimal read-eval-print loop to explore a day's parsed data.
+//!
+//! Each day registers its own commands, operating on a state holding its
+//! parsed input. `help` and `quit` are always available.
+use std::io::{BufRead, Write};
+use std::str::FromStr;
+
+use crate::{Error, Result};
+
+type Handler<S> = Box<dyn Fn(&mut S, &[&str]) -> Result<String>>;
+
+struct Command<S> {
+    name: &'static str,
+    usage: &'static str,
+    help: &'static str,
+    handler: Handler<S>,
+}
+
+/// Read-eval-print loop over a state `S`.
+pub struct Repl<S> {
+    prompt: String,
+    state: S,
+    commands: Vec<Command<S>>,
+}
+
+impl<S> Repl<S> {
+    pub fn new(prompt: &str, state: S) -> Self {
+        Self {
+            prompt: prompt.to_string(),
+            state,
+            commands: Vec::new(),
+        }
+    }
+
+    /// Register a command. `usage` lists its arguments and `help` describes it,
+    /// both are printed by `help`. The handler receives the command's arguments,
+    /// split on whitespace, and returns the text to print.
+    pub fn command(
+        mut self,
+        name: &'static str,
+        usage: &'static str,
+        help: &'static str,
+        handler: impl Fn(&mut S, &[&str]) -> Result<String> + 'static,
+    ) -> Self {
+        self.commands.push(Command {
+            name,
+            usage,
+            help,
+            handler: Box::new(handler),
+        });
+        self
+    }
+
+    fn help(&self) -> String {
+        let mut help = String::from("Commands:\n");
+        for command in &self.commands {
+            let signature = format!("{} {}", command.name, command.usage);
+            help.push_str(&format!(
+                "  {:<28} {}\n",
+                signature.trim_end(),
+                command.help
+            ));
+        }
+        help.push_str(&format!("  {:<28} {}\n", "help", "Show this message"));
+        help.push_str(&format!("  {:<28} {}", "quit", "Leave the REPL"));
+        help
+    }
+
+    /// Execute a single line, returning the text to print, or `None` if the
+    /// line asks to leave the REPL.
+    pub fn execute(&mut self, line: &str) -> Option<Result<String>> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+
+        match words.as_slice() {
+            [] => Some(Ok(String::new())),
+            ["quit" | "exit", ..] => None,
+            ["help", ..] => Some(Ok(self.help())),
+            [name, args @ ..] => Some(
+                match self.commands.iter().find(|command| command.name == *name) {
+                    Some(command) => (command.handler)(&mut self.state, args),
+                    None => Err(Error::ParseError(format!(
+                        "unknown command '{name}', try 'help'"
+                    ))),
+                },
+            ),
+        }
+    }
+
+    /// Run the loop until `quit` or the end of `input`. Command errors are
+    /// printed and do not stop the loop.
+    pub fn run(&mut self, input: impl BufRead, mut output: impl Write) -> Result<()> {
+        write!(output, "{}", self.prompt)?;
+        output.flush()?;
+
+        for line in input.lines() {
+            match self.execute(&line?) {
+                None => break,
+                Some(Ok(text)) if text.is_empty() => (),
+                Some(Ok(text)) => writeln!(output, "{text}")?,
+                Some(Err(err)) => writeln!(output, "error: {err}")?,
+            }
+            write!(output, "{}", self.prompt)?;
+            output.flush()?;
+        }
+
+        Ok(())
+    }
+
+    /// Run the loop on the standard input and output.
+    pub fn run_stdio(&mut self) -> Result<()> {
+        self.run(std::io::stdin().lock(), std::io::stdout())
+    }
+}
+
+/// Parse the argument at `index`, named `name` in error messages.
+pub fn parse_arg<T: FromStr>(args: &[&str], index: usize, name: &str) -> Result<T> {
+    let arg = args
+        .get(index)
+        .ok_or_else(|| Error::ParseError(format!("missing argument <{name}>")))?;
+    arg.parse()
+        .map_err(|_| Error::ParseError(format!("invalid <{name}>: '{arg}'")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counter() -> Repl<i64> {
+        Repl::new("> ", 0)
+            .command("add", "<n>", "Add n to the counter", |count, args| {
+                *count += parse_arg::<i64>(args, 0, "n")?;
+                Ok(count.to_string())
+            })
+            .command("show", "", "Show the counter", |count, _| {
+                Ok(count.to_string())
+            })
+    }
+
+    #[test]
+    fn execute_commands() {
+        let mut repl = counter();
+
+        assert!(matches!(repl.execute("add 3"), Some(Ok(text)) if text == "3"));
+        assert!(matches!(repl.execute("  add   -5 "), Some(Ok(text)) if text == "-2"));
+        assert!(matches!(
+            repl.execute("add"),
+            Some(Err(Error::ParseError(_)))
+        ));
+        assert!(matches!(
+            repl.execute("add x"),
+            Some(Err(Error::ParseError(_)))
+        ));
+        assert!(matches!(
+            repl.execute("unknown"),
+            Some(Err(Error::ParseError(_)))
+        ));
+        assert!(matches!(repl.execute("help"), Some(Ok(text)) if text.contains("add <n>")));
+        assert!(repl.execute("quit").is_none());
+    }
+
+    #[test]
+    fn run_loop() -> Result<()> {
+        let mut output = Vec::new();
+        counter().run("add 2\nfoo\nshow\nquit\nshow\n".as_bytes(), &mut output)?;
+
+        assert_eq!(
+            String::from_utf8_lossy(&output),
+            "> 2\n> error: Parse error: unknown command 'foo', try 'help'\n> 2\n> "
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,99 @@
+//! Runner for the days of the workspace.
+//!
+//! `aoc repl <day> [input]` loads the input of a day, `../inputs/<day>.txt` by
+//! default, and starts the REPL of that day to explore its parsed data.
+use aoc_lib::{Error, Result};
+
+/// Days with a REPL.
+const REPL_DAYS: [u8; 2] = [3, 7];
+
+/// Command-line command.
+#[derive(Debug, PartialEq, Eq)]
+enum Command {
+    Repl { day: u8, input_path: Option<String> },
+}
+
+impl Command {
+    /// Parse a command: `repl <day> [input]`.
+    fn from_args(args: impl IntoIterator<Item = String>) -> Result<Self> {
+        let args: Vec<String> = args.into_iter().collect();
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+        match args.as_slice() {
+            ["repl", day, rest @ ..] if rest.len() <= 1 => {
+                let day: u8 = day
+                    .parse()
+                    .map_err(|_| Error::ParseError(format!("invalid day '{day}'")))?;
+                if !REPL_DAYS.contains(&day) {
+                    return Err(Error::ParseError(format!(
+                        "no REPL for day {day}, expected one of {REPL_DAYS:?}"
+                    )));
+                }
+                Ok(Self::Repl {
+                    day,
+                    input_path: rest.first().map(|path| path.to_string()),
+                })
+            }
+            _ => Err(Error::ParseError(
+                "usage: aoc repl <day> [input]".to_string(),
+            )),
+        }
+    }
+}
+
+/// Load the input of `day` and run its REPL on stdin and stdout.
+fn repl(day: u8, input_path: Option<&str>) -> Result<()> {
+    let default_path = format!("../inputs/{day:02}.txt");
+    let input = std::fs::read_to_string(input_path.unwrap_or(&default_path))?;
+
+    match day {
+        3 => {
+            let (counting, gear, neighbourhood) = Default::default();
+            let schematic =
+                day03::edit::EditableSchematic::new(&input, counting, gear, neighbourhood)?;
+            day03::repl::repl(schematic).run_stdio()
+        }
+        7 => day07::repl::repl(day07::parse_input(&input)?).run_stdio(),
+        _ => unreachable!("days are checked when parsing the command"),
+    }
+}
+
+fn main() -> Result<()> {
+    match Command::from_args(std::env::args().skip(1))? {
+        Command::Repl { day, input_path } => repl(day, input_path.as_deref()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Result<Command> {
+        Command::from_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn parse_args() -> Result<()> {
+        assert_eq!(
+            args(&["repl", "3"])?,
+            Command::Repl {
+                day: 3,
+                input_path: None
+            }
+        );
+        assert_eq!(
+            args(&["repl", "07", "input.txt"])?,
+            Command::Repl {
+                day: 7,
+                input_path: Some("input.txt".to_string())
+            }
+        );
+        assert!(args(&[]).is_err());
+        assert!(args(&["repl"]).is_err());
+        assert!(args(&["repl", "three"]).is_err());
+        assert!(args(&["repl", "3", "a.txt", "b.txt"]).is_err());
+        assert!(args(&["repl", "1"]).is_err());
+
+        Ok(())
+    }
+}
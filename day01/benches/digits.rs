@@ -0,0 +1,59 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use day01::ascii;
+use day01::calibration::str_to_double_digit_number;
+
+/// Generate an ASCII calibration document of `lines` lines of `width` letters,
+/// each with two digits inserted at random positions.
+fn document(lines: usize, width: usize) -> String {
+    let mut seed: u64 = 0x2023_0001;
+    let mut next = move || {
+        seed = seed
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (seed >> 33) as usize
+    };
+
+    let mut document = String::new();
+    for _ in 0..lines {
+        let (a, b) = (next() % width, next() % width);
+        for i in 0..width {
+            if i == a || i == b {
+                document.push(char::from(b'0' + (next() % 10) as u8));
+            }
+            document.push(char::from(b'a' + (next() % 26) as u8));
+        }
+        document.push('\n');
+    }
+    document
+}
+
+fn bench_digits(c: &mut Criterion) {
+    let mut group = c.benchmark_group("digits");
+
+    for width in [16, 256, 4096] {
+        let input = document(1_000, width);
+        group.throughput(Throughput::Bytes(input.len() as u64));
+
+        group.bench_with_input(BenchmarkId::new("chars", width), &input, |b, input| {
+            b.iter(|| {
+                input
+                    .lines()
+                    .filter_map(|line| str_to_double_digit_number(line, false))
+                    .sum::<usize>()
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("ascii", width), &input, |b, input| {
+            b.iter(|| {
+                assert!(input.is_ascii());
+                ascii::lines(input)
+                    .filter_map(|line| ascii::double_digit_number(line.as_bytes()))
+                    .sum::<usize>()
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_digits);
+criterion_main!(benches);
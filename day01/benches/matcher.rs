@@ -0,0 +1,83 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use day01::matcher::Matcher;
+
+const NUMBERS: [&str; 20] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "0", "1", "2",
+    "3", "4", "5", "6", "7", "8", "9",
+];
+
+/// The `starts_with` scan used before the automaton matcher, as a baseline.
+fn naive(input: &str) -> Option<usize> {
+    let first_digit = (0..input.len()).find_map(|i| {
+        NUMBERS
+            .iter()
+            .enumerate()
+            .find_map(|(j, num)| input[i..].starts_with(num).then_some(j % 10))
+    });
+    let last_digit = (0..input.len()).rev().find_map(|i| {
+        NUMBERS
+            .iter()
+            .enumerate()
+            .find_map(|(j, num)| input[i..].starts_with(num).then_some(j % 10))
+    });
+
+    Some(first_digit? * 10 + last_digit?)
+}
+
+fn automaton(matcher: &Matcher<usize>, input: &str) -> Option<usize> {
+    Some(matcher.find_first(input)?.value * 10 + matcher.find_last(input)?.value)
+}
+
+/// Generate a calibration document of `lines` lines of `width` letters, each
+/// with two number words or digits inserted at random positions.
+fn document(lines: usize, width: usize) -> String {
+    let mut seed: u64 = 0x2023_0001;
+    let mut next = move || {
+        seed = seed
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (seed >> 33) as usize
+    };
+
+    let mut document = String::new();
+    for _ in 0..lines {
+        let (a, b) = (next() % width, next() % width);
+        for i in 0..width {
+            if i == a || i == b {
+                document.push_str(NUMBERS[next() % 20]);
+            }
+            document.push(char::from(b'a' + (next() % 26) as u8));
+        }
+        document.push('\n');
+    }
+    document
+}
+
+fn bench_matchers(c: &mut Criterion) {
+    let matcher = Matcher::new(NUMBERS.iter().enumerate().map(|(i, n)| (*n, i % 10)));
+    let mut group = c.benchmark_group("calibration");
+
+    for width in [16, 256, 4096] {
+        let input = document(1_000, width);
+        group.throughput(Throughput::Bytes(input.len() as u64));
+
+        group.bench_with_input(
+            BenchmarkId::new("starts_with", width),
+            &input,
+            |b, input| b.iter(|| input.lines().filter_map(naive).sum::<usize>()),
+        );
+        group.bench_with_input(BenchmarkId::new("automaton", width), &input, |b, input| {
+            b.iter(|| {
+                input
+                    .lines()
+                    .filter_map(|line| automaton(&matcher, line))
+                    .sum::<usize>()
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_matchers);
+criterion_main!(benches);
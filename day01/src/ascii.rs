@@ -0,0 +1,148 @@
+//! Byte-level scanning of ASCII documents, eight bytes at a time.
+//!
+//! Only valid on ASCII text: callers check it once for the whole document with
+//! [`str::is_ascii`] and fall back to the char-based functions otherwise.
+
+/// `0x0101010101010101`, to repeat a byte in all the bytes of a word.
+const ONES: u64 = u64::MAX / 255;
+
+/// Set the high bit of each byte of `word` holding an ASCII digit.
+///
+/// Each byte must be below 128, so that no byte operation carries into its
+/// neighbour: `low + 80` has its high bit set from `'0'` up, `185 - low` below
+/// `':'`.
+#[inline]
+fn digit_mask(word: u64) -> u64 {
+    let low = word & (ONES * 127);
+    let above_slash = low + ONES * (127 - (b'0' as u64 - 1));
+    let below_colon = ONES * (127 + b'9' as u64 + 1) - low;
+
+    above_slash & below_colon & !word & (ONES * 128)
+}
+
+fn ascii_digit(byte: u8) -> Option<usize> {
+    byte.is_ascii_digit().then(|| (byte - b'0') as usize)
+}
+
+/// Value of the first ASCII digit of `line`.
+pub fn first_digit(line: &[u8]) -> Option<usize> {
+    let chunks = line.chunks_exact(8);
+    let remainder = chunks.remainder();
+
+    for chunk in chunks {
+        let mask = digit_mask(u64::from_le_bytes(chunk.try_into().unwrap()));
+        if mask != 0 {
+            return ascii_digit(chunk[mask.trailing_zeros() as usize / 8]);
+        }
+    }
+    remainder.iter().find_map(|byte| ascii_digit(*byte))
+}
+
+/// Value of the last ASCII digit of `line`.
+pub fn last_digit(line: &[u8]) -> Option<usize> {
+    let chunks = line.rchunks_exact(8);
+    let remainder = chunks.remainder();
+
+    for chunk in chunks {
+        let mask = digit_mask(u64::from_le_bytes(chunk.try_into().unwrap()));
+        if mask != 0 {
+            return ascii_digit(chunk[7 - mask.leading_zeros() as usize / 8]);
+        }
+    }
+    remainder.iter().rev().find_map(|byte| ascii_digit(*byte))
+}
+
+/// Read the first and last ASCII digits of `line` as a two-digit number.
+pub fn double_digit_number(line: &[u8]) -> Option<usize> {
+    Some(first_digit(line)? * 10 + last_digit(line)?)
+}
+
+/// Iterate over the lines of `input` like [`str::lines`], finding line feeds with
+/// `memchr`. A carriage return ending a line is kept, it never is a digit.
+pub fn lines(input: &str) -> impl Iterator<Item = &str> {
+    let mut start = 0;
+    let ends = memchr::memchr_iter(b'\n', input.as_bytes()).chain(std::iter::once(input.len()));
+
+    ends.filter_map(move |end| {
+        let line = (start < end || end < input.len()).then(|| &input[start..end]);
+        start = end + 1;
+        line
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digit_mask_is_exact() {
+        for byte in 0..128u8 {
+            let word = u64::from_le_bytes([b'a', byte, b'/', b':', byte, b'0', b'9', b'z']);
+            let expected = [
+                false,
+                byte.is_ascii_digit(),
+                false,
+                false,
+                byte.is_ascii_digit(),
+                true,
+                true,
+                false,
+            ];
+            for (i, is_digit) in expected.iter().enumerate() {
+                assert_eq!(
+                    digit_mask(word) >> (8 * i + 7) & 1 == 1,
+                    *is_digit,
+                    "byte {byte} at {i}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn first_and_last_digits() {
+        for line in [
+            "",
+            "abc",
+            "1",
+            "a1b2c3d4e5f6g7h8i9",
+            "abcdefghijklmnop7",
+            "7abcdefghijklmnop",
+            "abcdefgh4ijklmnop",
+            "ab3cdefghijklmnopqrst9uv",
+            "treb7uchet",
+        ] {
+            let chars_first = line
+                .chars()
+                .find_map(|c| c.to_digit(10).map(|d| d as usize));
+            let chars_last = line
+                .chars()
+                .rev()
+                .find_map(|c| c.to_digit(10).map(|d| d as usize));
+
+            assert_eq!(first_digit(line.as_bytes()), chars_first, "{line}");
+            assert_eq!(last_digit(line.as_bytes()), chars_last, "{line}");
+        }
+    }
+
+    #[test]
+    fn lines_like_str_lines() {
+        for input in [
+            "",
+            "\n",
+            "a",
+            "a\n",
+            "a\nb",
+            "a\n\nb\n",
+            "\n\na",
+            "a\r\nb\r\n",
+        ] {
+            assert_eq!(
+                lines(input)
+                    .map(|line| line.trim_end_matches('\r'))
+                    .collect::<Vec<_>>(),
+                input.lines().collect::<Vec<_>>(),
+                "{input:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,271 @@
+//! Extraction of calibration values from documents.
+use aoc_lib::{Error, Result};
+
+use crate::ascii;
+use crate::dictionary::{Dictionary, MatchOptions};
+use crate::digits::digit;
+use crate::explain::{digit_tokens, matched_tokens, numeral_tokens, Explanation, Token};
+use crate::matcher::Matcher;
+use crate::numerals::{NumeralMatcher, Overlap};
+use crate::policy::DigitPolicy;
+
+/// Read the first and last digits of `input` as a two-digit number.
+pub fn str_to_double_digit_number(input: &str, unicode_digits: bool) -> Option<usize> {
+    let first_digit = input.chars().find_map(|c| digit(c, unicode_digits));
+    let last_digit = input.chars().rev().find_map(|c| digit(c, unicode_digits));
+
+    Some(first_digit? * 10 + last_digit?)
+}
+
+/// Read the first and last digits or words recognized by `matcher` in `input`
+/// as a two-digit number.
+pub fn str_literal_to_double_digit_number(matcher: &Matcher<usize>, input: &str) -> Option<usize> {
+    let first_digit = matcher.find_first(input)?.value;
+    let last_digit = matcher.find_last(input)?.value;
+
+    Some(first_digit * 10 + last_digit)
+}
+
+/// Sum of the calibration values of a document.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct CalibrationSum {
+    pub total: usize,
+    /// Numbers (starting at 1) of the lines skipped for not containing a number.
+    pub skipped_lines: Vec<usize>,
+}
+
+/// How numbers are read on a line.
+#[derive(Debug, Clone)]
+enum Reader {
+    Digits { unicode_digits: bool },
+    Words(Matcher<usize>),
+    Numerals(NumeralMatcher),
+}
+
+/// Extracts the calibration value of lines, from their digits and optionally
+/// their number words.
+#[derive(Debug, Clone)]
+pub struct CalibrationExtractor {
+    reader: Reader,
+    policy: DigitPolicy,
+}
+
+impl CalibrationExtractor {
+    /// Read digits only, as in part 1.
+    pub fn digits(options: MatchOptions) -> Self {
+        Self {
+            reader: Reader::Digits {
+                unicode_digits: options.unicode_digits,
+            },
+            policy: DigitPolicy::default(),
+        }
+    }
+
+    /// Read digits and the single-digit words of `dictionary`, as in part 2.
+    pub fn words_and_digits(dictionary: &Dictionary, options: MatchOptions) -> Self {
+        Self {
+            reader: Reader::Words(dictionary.matcher_with(options)),
+            policy: DigitPolicy::default(),
+        }
+    }
+
+    /// Read digits and compound English numerals.
+    pub fn numerals(options: MatchOptions, overlap: Overlap) -> Self {
+        Self {
+            reader: Reader::Numerals(NumeralMatcher::new(options, overlap)),
+            policy: DigitPolicy::default(),
+        }
+    }
+
+    /// Combine the numbers of a line with `policy`, first-last by default.
+    pub fn with_policy(mut self, policy: DigitPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Find the numbers of `line`, with their position, in reading order.
+    pub fn tokens(&self, line: &str) -> Vec<Token> {
+        match &self.reader {
+            Reader::Digits { unicode_digits } => digit_tokens(line, *unicode_digits),
+            Reader::Words(matcher) => matched_tokens(matcher, line),
+            Reader::Numerals(matcher) => numeral_tokens(matcher, line),
+        }
+    }
+
+    /// Find the numbers of `line`, in reading order.
+    pub fn numbers(&self, line: &str) -> Vec<usize> {
+        match &self.reader {
+            Reader::Digits { unicode_digits } => line
+                .chars()
+                .filter_map(|c| digit(c, *unicode_digits))
+                .collect(),
+            Reader::Words(matcher) => matcher.find_all(line).iter().map(|m| m.value).collect(),
+            Reader::Numerals(matcher) => matcher.find_all(line).iter().map(|m| m.value).collect(),
+        }
+    }
+
+    /// Get the calibration value of `line`, or `None` if it has no number.
+    pub fn value(&self, line: &str) -> Result<Option<usize>> {
+        // Only the ends of the line matter, no need to find all digits.
+        match (&self.reader, self.policy) {
+            (Reader::Digits { unicode_digits }, DigitPolicy::FirstLast) => {
+                Ok(str_to_double_digit_number(line, *unicode_digits))
+            }
+            (Reader::Words(matcher), DigitPolicy::FirstLast) => {
+                Ok(str_literal_to_double_digit_number(matcher, line))
+            }
+            (_, policy) => policy.apply(&self.numbers(line)),
+        }
+    }
+
+    /// Explain the calibration value of `line`, numbered `line_number`.
+    pub fn explain(&self, line_number: usize, line: &str) -> Result<Explanation> {
+        Explanation::new(line_number, line, self.tokens(line), self.policy)
+    }
+
+    /// Sum the calibration values of all lines. Lines without a number are an
+    /// error, unless `lenient` is set, in which case they are skipped and reported.
+    ///
+    /// Digits of ASCII documents are found byte by byte, eight at a time.
+    pub fn sum(&self, input: &str, lenient: bool) -> Result<CalibrationSum> {
+        match (&self.reader, self.policy) {
+            (Reader::Digits { .. }, DigitPolicy::FirstLast) if input.is_ascii() => sum_lines(
+                ascii::lines(input),
+                |line| Ok(ascii::double_digit_number(line.as_bytes())),
+                lenient,
+            ),
+            _ => sum_lines(input.lines(), |line| self.value(line), lenient),
+        }
+    }
+}
+
+/// Sum the values of `lines`, see [`CalibrationExtractor::sum`].
+fn sum_lines<'a>(
+    lines: impl Iterator<Item = &'a str>,
+    value: impl Fn(&str) -> Result<Option<usize>>,
+    lenient: bool,
+) -> Result<CalibrationSum> {
+    lines
+        .enumerate()
+        .try_fold(CalibrationSum::default(), |mut sum, (i, line)| {
+            match value(line)? {
+                Some(value) => {
+                    sum.total = sum.total.checked_add(value).ok_or_else(|| {
+                        Error::OverflowError(format!(
+                            "line {}: adding {value} to {} overflows a usize",
+                            i + 1,
+                            sum.total
+                        ))
+                    })?
+                }
+                None if lenient => sum.skipped_lines.push(i + 1),
+                None => {
+                    return Err(Error::ParseError(format!(
+                        "line {}: '{}' should contain a number",
+                        i + 1,
+                        line.trim_end_matches('\r')
+                    )))
+                }
+            }
+            Ok(sum)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overlapping_words() {
+        let matcher = Dictionary::english().matcher();
+
+        assert_eq!(
+            str_literal_to_double_digit_number(&matcher, "eightwo"),
+            Some(82)
+        );
+        assert_eq!(
+            str_literal_to_double_digit_number(&matcher, "xoneightx"),
+            Some(18)
+        );
+        assert_eq!(
+            str_literal_to_double_digit_number(&matcher, "sevenine"),
+            Some(79)
+        );
+        assert_eq!(str_literal_to_double_digit_number(&matcher, "abc"), None);
+    }
+
+    #[test]
+    fn extractors() -> Result<()> {
+        let options = MatchOptions::default();
+        let digits = CalibrationExtractor::digits(options);
+        let words = CalibrationExtractor::words_and_digits(&Dictionary::english(), options);
+        let numerals = CalibrationExtractor::numerals(options, Overlap::Share);
+
+        assert_eq!(digits.value("abcone2threexyz")?, Some(22));
+        assert_eq!(words.value("abcone2threexyz")?, Some(13));
+        assert_eq!(numerals.value("twenty-one2")?, Some(212));
+        assert_eq!(words.numbers("zoneight234"), vec![1, 8, 2, 3, 4]);
+        assert_eq!(
+            words.with_policy(DigitPolicy::Sum).value("zoneight234")?,
+            Some(18)
+        );
+        assert_eq!(digits.value("abc")?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn ascii_fast_path() -> Result<()> {
+        let digits = CalibrationExtractor::digits(MatchOptions::default());
+        let ascii = "1abc2\r\nabcdefghijkl\r\n\npqr3stu8vwxyz0123\n";
+        let non_ascii = ascii.replace("abcdefghijkl", "abcdéfghijkl");
+
+        assert_eq!(digits.sum(ascii, true)?, digits.sum(&non_ascii, true)?);
+        assert_eq!(
+            digits.sum(ascii, true)?,
+            CalibrationSum {
+                total: 12 + 33,
+                skipped_lines: vec![2, 3],
+            }
+        );
+        match digits.sum(ascii, false) {
+            Err(Error::ParseError(err)) => {
+                assert_eq!(err, "line 2: 'abcdefghijkl' should contain a number")
+            }
+            other => panic!("expected a parse error, got {other:?}"),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn sum_overflow() -> Result<()> {
+        let digits = CalibrationExtractor::digits(MatchOptions::default())
+            .with_policy(DigitPolicy::Concatenate);
+        let max = usize::MAX.to_string();
+
+        assert_eq!(digits.sum(&max, false)?.total, usize::MAX);
+        assert!(matches!(
+            digits.sum(&format!("{max}\n{max}\n"), false),
+            Err(Error::OverflowError(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn explain_with_extractor() -> Result<()> {
+        let words =
+            CalibrationExtractor::words_and_digits(&Dictionary::english(), Default::default());
+        let explanation = words.explain(2, "xtwone3four")?;
+
+        assert_eq!(explanation.line_number, 2);
+        assert_eq!(explanation.value, words.value("xtwone3four")?);
+        assert_eq!(
+            explanation.first.map(|token| token.text),
+            Some("two".to_string())
+        );
+
+        Ok(())
+    }
+}
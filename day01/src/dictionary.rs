@@ -0,0 +1,238 @@
+//! Number-word dictionaries used to read spelled-out digits.
+use std::str::FromStr;
+
+use aoc_lib::{Error, Result};
+
+use crate::digits::unicode_digits;
+use crate::matcher::Matcher;
+
+const ENGLISH: [&str; 10] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+const FRENCH: [&str; 10] = [
+    "zéro", "un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf",
+];
+const GERMAN: [&str; 10] = [
+    "null", "eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun",
+];
+const SPANISH: [&str; 10] = [
+    "cero", "uno", "dos", "tres", "cuatro", "cinco", "seis", "siete", "ocho", "nueve",
+];
+
+/// Names of the built-in dictionaries, usable with [`Dictionary::builtin`].
+pub const BUILTIN_LANGUAGES: [&str; 4] = ["english", "french", "german", "spanish"];
+
+/// How a [`Dictionary`]'s matcher recognizes numbers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MatchOptions {
+    /// Recognize decimal digits of all scripts, not only ASCII ones.
+    pub unicode_digits: bool,
+    /// Match words regardless of case.
+    pub case_insensitive: bool,
+}
+
+/// Words mapped to the digit they spell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dictionary {
+    words: Vec<(String, usize)>,
+}
+
+impl Dictionary {
+    fn from_digit_words(words: [&str; 10]) -> Self {
+        Self {
+            words: words
+                .iter()
+                .enumerate()
+                .map(|(digit, word)| (word.to_string(), digit))
+                .collect(),
+        }
+    }
+
+    pub fn english() -> Self {
+        Self::from_digit_words(ENGLISH)
+    }
+
+    pub fn french() -> Self {
+        Self::from_digit_words(FRENCH)
+    }
+
+    pub fn german() -> Self {
+        Self::from_digit_words(GERMAN)
+    }
+
+    pub fn spanish() -> Self {
+        Self::from_digit_words(SPANISH)
+    }
+
+    /// Get a built-in dictionary from its language name, see [`BUILTIN_LANGUAGES`].
+    pub fn builtin(language: &str) -> Option<Self> {
+        match language {
+            "english" => Some(Self::english()),
+            "french" => Some(Self::french()),
+            "german" => Some(Self::german()),
+            "spanish" => Some(Self::spanish()),
+            _ => None,
+        }
+    }
+
+    /// Read a dictionary from a file, see [`Dictionary::from_str`] for the format.
+    pub fn from_file(path: impl AsRef<std::path::Path>) -> Result<Self> {
+        std::fs::read_to_string(path)?.parse()
+    }
+
+    /// Iterate over the words and their digit.
+    pub fn words(&self) -> impl Iterator<Item = (&str, usize)> {
+        self.words
+            .iter()
+            .map(|(word, digit)| (word.as_str(), *digit))
+    }
+
+    /// Build a matcher recognizing the words of the dictionary and the ASCII digits,
+    /// each associated with its digit.
+    pub fn matcher(&self) -> Matcher<usize> {
+        self.matcher_with(MatchOptions::default())
+    }
+
+    /// Build a matcher recognizing the words of the dictionary and digits,
+    /// each associated with its digit.
+    pub fn matcher_with(&self, options: MatchOptions) -> Matcher<usize> {
+        let digits: Vec<(String, usize)> = unicode_digits()
+            .filter(|(c, _)| options.unicode_digits || c.is_ascii_digit())
+            .map(|(c, digit)| (c.to_string(), digit))
+            .collect();
+        let patterns = self.words().chain(
+            digits
+                .iter()
+                .map(|(digit_str, digit)| (digit_str.as_str(), *digit)),
+        );
+
+        if options.case_insensitive {
+            Matcher::case_insensitive(patterns)
+        } else {
+            Matcher::new(patterns)
+        }
+    }
+}
+
+impl Default for Dictionary {
+    fn default() -> Self {
+        Self::english()
+    }
+}
+
+impl FromStr for Dictionary {
+    type Err = Error;
+
+    /// Parse a dictionary with one `word digit` entry per line. Empty lines and
+    /// lines starting with `#` are ignored. A digit can be spelled by several words.
+    fn from_str(s: &str) -> Result<Self> {
+        let words = s
+            .lines()
+            .enumerate()
+            .map(|(line_number, line)| (line_number + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+            .map(|(line_number, line)| {
+                let (word, digit) = line.split_once(char::is_whitespace).ok_or_else(|| {
+                    Error::ParseError(format!(
+                        "line {line_number}: expected 'word digit', got '{line}'"
+                    ))
+                })?;
+                match digit.trim().parse() {
+                    Ok(digit @ 0..=9) => Ok((word.to_string(), digit)),
+                    _ => Err(Error::ParseError(format!(
+                        "line {line_number}: '{}' is not a digit",
+                        digit.trim()
+                    ))),
+                }
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self { words })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn calibration_value(dictionary: &Dictionary, line: &str) -> Option<usize> {
+        let matcher = dictionary.matcher();
+        Some(matcher.find_first(line)?.value * 10 + matcher.find_last(line)?.value)
+    }
+
+    #[test]
+    fn builtin_dictionaries() {
+        for language in BUILTIN_LANGUAGES {
+            let dictionary = Dictionary::builtin(language).expect("language is built in");
+            assert_eq!(dictionary.words().count(), 10);
+        }
+        assert_eq!(Dictionary::builtin("klingon"), None);
+    }
+
+    #[test]
+    fn overlapping_words_in_every_language() {
+        assert_eq!(
+            calibration_value(&Dictionary::english(), "xeightwo"),
+            Some(82)
+        );
+        assert_eq!(
+            calibration_value(&Dictionary::french(), "huitrois"),
+            Some(83)
+        );
+        assert_eq!(
+            calibration_value(&Dictionary::french(), "zéroneuf"),
+            Some(9)
+        );
+        assert_eq!(
+            calibration_value(&Dictionary::german(), "einsechs"),
+            Some(16)
+        );
+        assert_eq!(
+            calibration_value(&Dictionary::german(), "zweinsfünf"),
+            Some(25)
+        );
+        assert_eq!(
+            calibration_value(&Dictionary::spanish(), "unocho"),
+            Some(18)
+        );
+        assert_eq!(
+            calibration_value(&Dictionary::spanish(), "cincocho7"),
+            Some(57)
+        );
+    }
+
+    #[test]
+    fn match_options() {
+        let dictionary = Dictionary::french();
+        let default = dictionary.matcher();
+        let extended = dictionary.matcher_with(MatchOptions {
+            unicode_digits: true,
+            case_insensitive: true,
+        });
+
+        assert_eq!(default.find_first("٣HUIT").map(|m| m.value), None);
+        assert_eq!(extended.find_first("٣HUIT").map(|m| m.value), Some(3));
+        assert_eq!(extended.find_last("٣HUIT").map(|m| m.value), Some(8));
+        assert_eq!(extended.find_last("ZÉRO").map(|m| m.value), Some(0));
+    }
+
+    #[test]
+    fn parse_dictionary() -> Result<()> {
+        let dictionary: Dictionary = "# Italian, partial\nuno 1\n\n  due 2\ntre\t3\n".parse()?;
+
+        assert_eq!(
+            dictionary.words().collect::<Vec<_>>(),
+            vec![("uno", 1), ("due", 2), ("tre", 3)]
+        );
+        assert_eq!(calibration_value(&dictionary, "xduetrex"), Some(23));
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_invalid_dictionary() {
+        assert!("uno".parse::<Dictionary>().is_err());
+        assert!("uno one".parse::<Dictionary>().is_err());
+        assert!("dieci 10".parse::<Dictionary>().is_err());
+    }
+}
@@ -0,0 +1,74 @@
+//! Decimal digits of all Unicode scripts.
+
+/// Code points of the zero digit of each run of ten decimal digits (Unicode
+/// general category `Nd`), in increasing order.
+const ZEROS: [u32; 68] = [
+    0x0030, 0x0660, 0x06F0, 0x07C0, 0x0966, 0x09E6, 0x0A66, 0x0AE6, 0x0B66, 0x0BE6, 0x0C66, 0x0CE6,
+    0x0D66, 0x0DE6, 0x0E50, 0x0ED0, 0x0F20, 0x1040, 0x1090, 0x17E0, 0x1810, 0x1946, 0x19D0, 0x1A80,
+    0x1A90, 0x1B50, 0x1BB0, 0x1C40, 0x1C50, 0xA620, 0xA8D0, 0xA900, 0xA9D0, 0xA9F0, 0xAA50, 0xABF0,
+    0xFF10, 0x104A0, 0x10D30, 0x11066, 0x110F0, 0x11136, 0x111D0, 0x112F0, 0x11450, 0x114D0,
+    0x11650, 0x116C0, 0x11730, 0x118E0, 0x11950, 0x11C50, 0x11D50, 0x11DA0, 0x11F50, 0x16A60,
+    0x16AC0, 0x16B50, 0x1D7CE, 0x1D7D8, 0x1D7E2, 0x1D7EC, 0x1D7F6, 0x1E140, 0x1E2F0, 0x1E4F0,
+    0x1E950, 0x1FBF0,
+];
+
+/// Value of `c` if it is a decimal digit of any script, e.g. `'٣'` or `'３'`.
+pub fn unicode_digit(c: char) -> Option<usize> {
+    let code = c as u32;
+    let index = ZEROS.partition_point(|zero| *zero <= code).checked_sub(1)?;
+    let value = code - ZEROS[index];
+
+    (value < 10).then_some(value as usize)
+}
+
+/// Value of `c` if it is an ASCII digit, or any Unicode decimal digit if `unicode` is set.
+pub fn digit(c: char, unicode: bool) -> Option<usize> {
+    if unicode {
+        unicode_digit(c)
+    } else {
+        c.to_digit(10).map(|digit| digit as usize)
+    }
+}
+
+/// Iterate over all Unicode decimal digits with their value.
+pub fn unicode_digits() -> impl Iterator<Item = (char, usize)> {
+    ZEROS.into_iter().flat_map(|zero| {
+        (0..10).filter_map(move |value| char::from_u32(zero + value).map(|c| (c, value as usize)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digits_of_several_scripts() {
+        assert_eq!(unicode_digit('7'), Some(7));
+        assert_eq!(unicode_digit('٣'), Some(3));
+        assert_eq!(unicode_digit('۹'), Some(9));
+        assert_eq!(unicode_digit('३'), Some(3));
+        assert_eq!(unicode_digit('５'), Some(5));
+        assert_eq!(unicode_digit('𝟘'), Some(0));
+        assert_eq!(unicode_digit('a'), None);
+        assert_eq!(unicode_digit('½'), None);
+        assert_eq!(unicode_digit('Ⅻ'), None);
+    }
+
+    #[test]
+    fn ascii_only_digits() {
+        assert_eq!(digit('٣', false), None);
+        assert_eq!(digit('٣', true), Some(3));
+        assert_eq!(digit('3', false), Some(3));
+    }
+
+    #[test]
+    fn all_digits_are_numeric() {
+        let digits: Vec<_> = unicode_digits().collect();
+
+        assert_eq!(digits.len(), 680);
+        assert!(digits.iter().all(|(c, _)| c.is_numeric()));
+        assert!(digits
+            .iter()
+            .all(|(c, value)| unicode_digit(*c) == Some(*value)));
+    }
+}
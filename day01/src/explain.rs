@@ -0,0 +1,266 @@
+//! Per-line explanation of calibration values: which tokens were matched, where,
+//! and the resulting value.
+use std::fmt;
+
+use aoc_lib::{csv, Result};
+
+use crate::digits::digit;
+use crate::matcher::Matcher;
+use crate::numerals::NumeralMatcher;
+use crate::policy::DigitPolicy;
+
+/// Whether a token was written as a digit or spelled out as a word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    Digit,
+    Word,
+}
+
+impl fmt::Display for TokenKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TokenKind::Digit => write!(f, "digit"),
+            TokenKind::Word => write!(f, "word"),
+        }
+    }
+}
+
+/// Number found on a line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub kind: TokenKind,
+    /// Text of the token, as written on the line.
+    pub text: String,
+    /// Byte offset of the token in the line.
+    pub byte: usize,
+    /// Character offset of the token in the line.
+    pub char: usize,
+    pub value: usize,
+}
+
+impl Token {
+    fn new(line: &str, start: usize, end: usize, value: usize) -> Self {
+        let text = &line[start..end];
+        let mut chars = text.chars();
+        let kind = match (chars.next().and_then(|c| digit(c, true)), chars.next()) {
+            (Some(_), None) => TokenKind::Digit,
+            _ => TokenKind::Word,
+        };
+
+        Self {
+            kind,
+            text: text.to_string(),
+            byte: start,
+            char: line[..start].chars().count(),
+            value,
+        }
+    }
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} '{}'={} @{}/{}",
+            self.kind, self.text, self.value, self.byte, self.char
+        )
+    }
+}
+
+/// Find the digits of `line`, as tokens.
+pub fn digit_tokens(line: &str, unicode_digits: bool) -> Vec<Token> {
+    line.char_indices()
+        .filter_map(|(i, c)| {
+            digit(c, unicode_digits).map(|value| Token::new(line, i, i + c.len_utf8(), value))
+        })
+        .collect()
+}
+
+/// Find the words and digits recognized by `matcher` on `line`, as tokens.
+pub fn matched_tokens(matcher: &Matcher<usize>, line: &str) -> Vec<Token> {
+    matcher
+        .find_all(line)
+        .into_iter()
+        .map(|m| Token::new(line, m.start, m.end, m.value))
+        .collect()
+}
+
+/// Find the numerals and digits recognized by `matcher` on `line`, as tokens.
+pub fn numeral_tokens(matcher: &NumeralMatcher, line: &str) -> Vec<Token> {
+    matcher
+        .find_all(line)
+        .into_iter()
+        .map(|m| Token::new(line, m.start, m.end, m.value))
+        .collect()
+}
+
+/// Explanation of the calibration value of a line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation {
+    /// Line number, starting at 1.
+    pub line_number: usize,
+    pub line: String,
+    pub first: Option<Token>,
+    pub last: Option<Token>,
+    /// Calibration value, if the line contains a number.
+    pub value: Option<usize>,
+}
+
+impl Explanation {
+    /// Explain the value of `line` made by `policy` from all its `tokens`.
+    pub fn new(
+        line_number: usize,
+        line: &str,
+        tokens: Vec<Token>,
+        policy: DigitPolicy,
+    ) -> Result<Self> {
+        let digits: Vec<usize> = tokens.iter().map(|token| token.value).collect();
+
+        Ok(Self {
+            line_number,
+            line: line.to_string(),
+            value: policy.apply(&digits)?,
+            first: tokens.first().cloned(),
+            last: tokens.last().cloned(),
+        })
+    }
+
+    /// Header of the CSV export, see [`Explanation::to_csv`].
+    pub const CSV_HEADER: &'static str = "line,text,first_kind,first_text,first_value,first_byte,first_char,last_kind,last_text,last_value,last_byte,last_char,value";
+
+    /// Export as a CSV record, without the line terminator. Fields of missing
+    /// tokens and values are empty.
+    pub fn to_csv(&self) -> String {
+        let token_fields = |token: &Option<Token>| match token {
+            Some(token) => format!(
+                "{},{},{},{},{}",
+                token.kind,
+                csv::field(&token.text),
+                token.value,
+                token.byte,
+                token.char
+            ),
+            None => ",,,,".to_string(),
+        };
+
+        format!(
+            "{},{},{},{},{}",
+            self.line_number,
+            csv::field(&self.line),
+            token_fields(&self.first),
+            token_fields(&self.last),
+            self.value
+                .map_or_else(String::new, |value| value.to_string())
+        )
+    }
+}
+
+/// Print as a row of the annotated table: line number, first and last tokens,
+/// value and line text.
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let token =
+            |token: &Option<Token>| token.as_ref().map_or("-".to_string(), Token::to_string);
+        let value = self
+            .value
+            .map_or("-".to_string(), |value| value.to_string());
+
+        write!(
+            f,
+            "{:>5}  {:<24}  {:<24}  {:>6}  {}",
+            self.line_number,
+            token(&self.first),
+            token(&self.last),
+            value,
+            self.line
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dictionary::Dictionary;
+
+    #[test]
+    fn explain_overlapping_words() -> Result<()> {
+        let matcher = Dictionary::english().matcher();
+        let line = "éoneight";
+        let explanation = Explanation::new(
+            1,
+            line,
+            matched_tokens(&matcher, line),
+            DigitPolicy::FirstLast,
+        )?;
+
+        assert_eq!(
+            explanation.first,
+            Some(Token {
+                kind: TokenKind::Word,
+                text: "one".to_string(),
+                byte: 2,
+                char: 1,
+                value: 1,
+            })
+        );
+        assert_eq!(
+            explanation
+                .last
+                .as_ref()
+                .map(|token| (&token.text[..], token.byte, token.char)),
+            Some(("eight", 4, 3))
+        );
+        assert_eq!(explanation.value, Some(18));
+        assert_eq!(
+            explanation.to_csv(),
+            "1,éoneight,word,one,1,2,1,word,eight,8,4,3,18"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn explain_numerals() -> Result<()> {
+        let matcher = NumeralMatcher::new(Default::default(), Default::default());
+        let line = "6 twenty-one";
+        let explanation = Explanation::new(
+            1,
+            line,
+            numeral_tokens(&matcher, line),
+            DigitPolicy::FirstLast,
+        )?;
+
+        assert_eq!(
+            explanation.to_csv(),
+            "1,6 twenty-one,digit,6,6,0,0,word,twenty-one,21,2,2,621"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn explain_digits() -> Result<()> {
+        let line = "a,7\"b";
+        let explanation =
+            Explanation::new(3, line, digit_tokens(line, false), DigitPolicy::FirstLast)?;
+
+        assert_eq!(explanation.first, explanation.last);
+        assert_eq!(
+            explanation.first.as_ref().map(|token| token.kind),
+            Some(TokenKind::Digit)
+        );
+        assert_eq!(
+            explanation.to_csv(),
+            "3,\"a,7\"\"b\",digit,7,7,2,2,digit,7,7,2,2,77"
+        );
+
+        let empty = Explanation::new(4, "abc", digit_tokens("abc", false), DigitPolicy::FirstLast)?;
+        assert_eq!(empty.to_csv(), "4,abc,,,,,,,,,,,");
+        assert_eq!(
+            empty.to_string(),
+            "    4  -                         -                              -  abc"
+        );
+
+        Ok(())
+    }
+}
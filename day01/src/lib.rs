@@ -0,0 +1,8 @@
+pub mod ascii;
+pub mod calibration;
+pub mod dictionary;
+pub mod digits;
+pub mod explain;
+pub mod matcher;
+pub mod numerals;
+pub mod policy;
@@ -1,52 +1,179 @@
-const NUMBERS: [&str; 20] = [
-    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "0", "1", "2",
-    "3", "4", "5", "6", "7", "8", "9",
-];
+use aoc_lib::{Error, Result};
+use day01::calibration::{CalibrationExtractor, CalibrationSum};
+use day01::dictionary::{Dictionary, MatchOptions, BUILTIN_LANGUAGES};
+use day01::explain::Explanation;
+use day01::numerals::Overlap;
+use day01::policy::DigitPolicy;
 
-fn str_to_double_digit_number(input: &str) -> Option<usize> {
-    let first_digit = input.chars().find_map(|c| c.to_digit(10));
-    let last_digit = input.chars().rev().find_map(|c| c.to_digit(10));
+/// Output format of the explain mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExplainFormat {
+    Table,
+    Csv,
+}
+
+/// Command-line options.
+#[derive(Debug, Default)]
+struct Options {
+    dictionary: Dictionary,
+    match_options: MatchOptions,
+    /// How the numbers of a line make its calibration value.
+    policy: DigitPolicy,
+    /// Read compound English numerals in part 2, instead of single-digit words.
+    compound: bool,
+    /// How compound numerals sharing letters are read.
+    overlap: Overlap,
+    /// Skip lines without a number instead of failing.
+    lenient: bool,
+    /// Explain the value of each line instead of printing the sums.
+    explain: Option<ExplainFormat>,
+}
+
+impl Options {
+    /// Parse options: `--dictionary <language|path>` (English by default),
+    /// `--unicode-digits`, `--ignore-case`, `--policy <policy>` (first-last by
+    /// default), `--compound`, `--overlap <share|exclusive>` (share by default),
+    /// `--lenient` and `--explain <table|csv>`.
+    fn from_args(args: impl IntoIterator<Item = String>) -> Result<Self> {
+        let mut options = Self::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--dictionary" => {
+                    let name = args.next().ok_or_else(|| {
+                        Error::ParseError(format!(
+                            "--dictionary expects one of {} or a file path",
+                            BUILTIN_LANGUAGES.join(", ")
+                        ))
+                    })?;
+                    options.dictionary = Dictionary::builtin(&name)
+                        .map_or_else(|| Dictionary::from_file(&name), Ok)?;
+                }
+                "--unicode-digits" => options.match_options.unicode_digits = true,
+                "--ignore-case" => options.match_options.case_insensitive = true,
+                "--policy" => {
+                    options.policy = args
+                        .next()
+                        .ok_or_else(|| Error::ParseError("--policy expects a policy".to_string()))?
+                        .parse()?;
+                }
+                "--compound" => options.compound = true,
+                "--overlap" => {
+                    options.overlap = match args.next().as_deref() {
+                        Some("share") => Overlap::Share,
+                        Some("exclusive") => Overlap::Exclusive,
+                        _ => {
+                            return Err(Error::ParseError(
+                                "--overlap expects share or exclusive".to_string(),
+                            ))
+                        }
+                    };
+                }
+                "--lenient" => options.lenient = true,
+                "--explain" => {
+                    options.explain = match args.next().as_deref() {
+                        Some("table") => Some(ExplainFormat::Table),
+                        Some("csv") => Some(ExplainFormat::Csv),
+                        _ => {
+                            return Err(Error::ParseError(
+                                "--explain expects table or csv".to_string(),
+                            ))
+                        }
+                    };
+                }
+                _ => return Err(Error::ParseError(format!("unknown argument '{arg}'"))),
+            }
+        }
+
+        if options.compound && options.dictionary != Dictionary::english() {
+            return Err(Error::ParseError(
+                "compound numerals are only read in English".to_string(),
+            ));
+        }
+
+        Ok(options)
+    }
+}
+
+/// Build the extractor of the given part.
+fn extractor(part: usize, options: &Options) -> CalibrationExtractor {
+    let extractor = match part {
+        1 => CalibrationExtractor::digits(options.match_options),
+        _ if options.compound => {
+            CalibrationExtractor::numerals(options.match_options, options.overlap)
+        }
+        _ => CalibrationExtractor::words_and_digits(&options.dictionary, options.match_options),
+    };
+    extractor.with_policy(options.policy)
+}
 
-    Some((first_digit? * 10 + last_digit?) as usize)
+fn part_1(input: &str, options: &Options) -> Result<CalibrationSum> {
+    extractor(1, options).sum(input, options.lenient)
 }
 
-fn str_literal_to_double_digit_number(input: &str) -> Option<usize> {
-    let first_digit = (0..input.len()).find_map(|i| {
-        NUMBERS
-            .iter()
-            .enumerate()
-            .find_map(|(j, num)| input[i..].starts_with(num).then_some(j % 10))
-    });
-    let last_digit = (0..input.len()).rev().find_map(|i| {
-        NUMBERS
-            .iter()
-            .enumerate()
-            .find_map(|(j, num)| input[i..].starts_with(num).then_some(j % 10))
-    });
-
-    Some(first_digit? * 10 + last_digit?)
+fn part_2(input: &str, options: &Options) -> Result<CalibrationSum> {
+    extractor(2, options).sum(input, options.lenient)
 }
 
-fn parse_input(input: &str, parse_line: fn(&str) -> Option<usize>) -> usize {
+/// Explain the calibration value of each line, for the given part.
+fn explain(input: &str, part: usize, options: &Options) -> Result<Vec<Explanation>> {
+    let extractor = extractor(part, options);
     input
         .lines()
-        .map(|line| parse_line(line).expect(&format!("{line} should contain a number")))
-        .sum()
+        .enumerate()
+        .map(|(i, line)| extractor.explain(i + 1, line))
+        .collect()
 }
 
-fn part_1(input: &str) -> usize {
-    parse_input(input, str_to_double_digit_number)
+fn print_explanations(input: &str, options: &Options, format: ExplainFormat) -> Result<()> {
+    match format {
+        ExplainFormat::Table => {
+            for part in [1, 2] {
+                println!("Day 01 - Part {part} (kind 'text'=value @byte/char)");
+                println!(
+                    "{:>5}  {:<24}  {:<24}  {:>6}  text",
+                    "line", "first", "last", "value"
+                );
+                for explanation in explain(input, part, options)? {
+                    println!("{explanation}");
+                }
+            }
+        }
+        ExplainFormat::Csv => {
+            println!("part,{}", Explanation::CSV_HEADER);
+            for part in [1, 2] {
+                for explanation in explain(input, part, options)? {
+                    println!("{part},{}", explanation.to_csv());
+                }
+            }
+        }
+    }
+
+    Ok(())
 }
 
-fn part_2(input: &str) -> usize {
-    parse_input(input, str_literal_to_double_digit_number)
+fn print_part(part: usize, sum: &CalibrationSum) {
+    println!("Day 01 - Part {part}: {}", sum.total);
+    if !sum.skipped_lines.is_empty() {
+        let lines: Vec<String> = sum.skipped_lines.iter().map(usize::to_string).collect();
+        eprintln!(
+            "Part {part} skipped lines without a number: {}",
+            lines.join(", ")
+        );
+    }
 }
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
+fn main() -> Result<()> {
+    let options = Options::from_args(std::env::args().skip(1))?;
     let input = std::fs::read_to_string("../inputs/01.txt")?;
 
-    println!("Day 01 - Part 1: {}", part_1(&input));
-    println!("Day 01 - Part 2: {}", part_2(&input));
+    if let Some(format) = options.explain {
+        return print_explanations(&input, &options, format);
+    }
+
+    print_part(1, &part_1(&input, &options)?);
+    print_part(2, &part_2(&input, &options)?);
 
     Ok(())
 }
@@ -56,14 +183,161 @@ mod tests {
     use crate::*;
 
     #[test]
-    fn part_one() {
+    fn part_one() -> Result<()> {
+        let input = include_str!("../../inputs/tests/01-1.txt");
+        assert_eq!(part_1(input, &Options::default())?.total, 142);
+
+        Ok(())
+    }
+
+    #[test]
+    fn part_two() -> Result<()> {
+        let input = include_str!("../../inputs/tests/01-2.txt");
+        assert_eq!(part_2(input, &Options::default())?.total, 281);
+
+        Ok(())
+    }
+
+    #[test]
+    fn non_ascii_lines() -> Result<()> {
+        let ascii = Options::default();
+        let unicode = Options {
+            match_options: MatchOptions {
+                unicode_digits: true,
+                case_insensitive: true,
+            },
+            ..Options::default()
+        };
+
+        assert_eq!(part_1("é1ü٣", &ascii)?.total, 11);
+        assert_eq!(part_1("é1ü٣", &unicode)?.total, 13);
+        assert_eq!(part_1("ｘ５ÿ9", &unicode)?.total, 59);
+        assert_eq!(part_2("ñEIGHTwo5", &ascii)?.total, 55);
+        assert_eq!(part_2("ñEIGHTwo5", &unicode)?.total, 85);
+        assert_eq!(part_2("ÉIGHTWO", &unicode)?.total, 22);
+        assert_eq!(part_2("ñ٣€sevenÜ", &unicode)?.total, 37);
+        assert_eq!(part_2("ñ٣€sevenÜ", &ascii)?.total, 77);
+
+        Ok(())
+    }
+
+    #[test]
+    fn digit_policies() -> Result<()> {
+        let input = "two1nine3\nabc4x5yz\n7pqrstsixteen";
+        let with_policy = |policy| Options {
+            policy,
+            ..Options::default()
+        };
+
+        assert_eq!(
+            part_1(input, &with_policy(DigitPolicy::Concatenate))?.total,
+            13 + 45 + 7
+        );
+        assert_eq!(
+            part_1(input, &with_policy(DigitPolicy::Sum))?.total,
+            4 + 9 + 7
+        );
+        assert_eq!(
+            part_2(input, &with_policy(DigitPolicy::Concatenate))?.total,
+            2193 + 45 + 76
+        );
+        assert_eq!(
+            part_2(input, &with_policy(DigitPolicy::First(2)))?.total,
+            21 + 45 + 76
+        );
+        assert_eq!(
+            part_2(input, &with_policy(DigitPolicy::Last(1)))?.total,
+            3 + 5 + 6
+        );
+        assert_eq!(
+            part_2(input, &with_policy(DigitPolicy::Sum))?.total,
+            15 + 9 + 13
+        );
+        assert_eq!(
+            part_2("eightwothree", &with_policy(DigitPolicy::Concatenate))?.total,
+            823
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn explanations_match_values() -> Result<()> {
+        let options = Options::default();
+        let values = |input, part| -> Result<usize> {
+            Ok(explain(input, part, &options)?
+                .iter()
+                .filter_map(|explanation| explanation.value)
+                .sum())
+        };
+
         let input = include_str!("../../inputs/tests/01-1.txt");
-        assert_eq!(part_1(input), 142);
+        assert_eq!(values(input, 1)?, part_1(input, &options)?.total);
+        let input = include_str!("../../inputs/tests/01-2.txt");
+        assert_eq!(values(input, 2)?, part_2(input, &options)?.total);
+
+        Ok(())
     }
 
     #[test]
-    fn part_two() {
+    fn compound_numerals() -> Result<()> {
+        let input = "twenty-one and 5\nxnineteen\none hundred and five\neightwo";
+        let compound = |overlap| Options {
+            compound: true,
+            overlap,
+            ..Options::default()
+        };
+
+        assert_eq!(
+            part_2(input, &compound(Overlap::Share))?.total,
+            215 + 1919 + 105105 + 82
+        );
+        assert_eq!(
+            part_2(input, &compound(Overlap::Exclusive))?.total,
+            215 + 1919 + 105105 + 88
+        );
+        // Only "7pqrstsixteen" changes, from 76 to 716.
         let input = include_str!("../../inputs/tests/01-2.txt");
-        assert_eq!(part_2(input), 281);
+        assert_eq!(
+            part_2(input, &compound(Overlap::Share))?.total,
+            281 - 76 + 716
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn compound_numerals_in_english_only() {
+        let args = ["--dictionary", "french", "--compound"].map(String::from);
+        assert!(Options::from_args(args).is_err());
+    }
+
+    #[test]
+    fn line_without_number() {
+        let input = "1abc2\npqr3stu8vwx\nabcdef\ntreb7uchet";
+
+        match part_1(input, &Options::default()) {
+            Err(Error::ParseError(err)) => assert!(err.starts_with("line 3:")),
+            other => panic!("expected a parse error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn lenient_mode() -> Result<()> {
+        let input = "1abc2\nabcdef\npqr3stu8vwx\n\ntreb7uchet";
+        let lenient = Options {
+            lenient: true,
+            ..Options::default()
+        };
+
+        assert_eq!(
+            part_1(input, &lenient)?,
+            CalibrationSum {
+                total: 12 + 38 + 77,
+                skipped_lines: vec![2, 4],
+            }
+        );
+
+        Ok(())
     }
 }
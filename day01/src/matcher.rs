@@ -0,0 +1,445 @@
+//! Multi-pattern matcher finding the first and last pattern occurrences of a text
+//! in a single pass.
+//!
+//! Patterns are compiled into two Aho–Corasick automatons: one for the patterns,
+//! scanned from the start of the text, and one for the reversed patterns,
+//! scanned from its end. Automatons work on bytes, so a match always starts
+//! and ends on a char boundary.
+use std::borrow::Cow;
+
+/// A pattern occurrence in a text, with byte offsets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match<V> {
+    /// Offset of the first byte of the match.
+    pub start: usize,
+    /// Offset of the byte following the match.
+    pub end: usize,
+    /// Value associated with the matched pattern.
+    pub value: V,
+}
+
+/// Deterministic automaton recognizing a set of byte patterns.
+///
+/// Bytes are grouped in classes, all bytes that appear in no pattern sharing
+/// class 0, so that each state only needs one transition per class. States
+/// are identified by the offset of their transitions in the flattened table.
+#[derive(Debug, Clone)]
+struct Automaton {
+    /// Class of each byte value.
+    classes: [u16; 256],
+    /// Transitions of all states, failure links already resolved.
+    transitions: Vec<u32>,
+    /// Longest pattern ending in each state, as (pattern index, pattern length).
+    /// Only indices of states are used.
+    outputs: Vec<Option<(usize, usize)>>,
+    /// Pattern spelled by each state, if any, as (pattern index, pattern length).
+    patterns: Vec<Option<(usize, usize)>>,
+    /// Failure link of each state: the state of its longest proper suffix.
+    failures: Vec<usize>,
+}
+
+impl Automaton {
+    fn new<'a>(patterns: impl IntoIterator<Item = &'a [u8]> + Clone) -> Self {
+        let mut classes = [0u16; 256];
+        let mut stride = 1;
+        for byte in patterns.clone().into_iter().flatten() {
+            if classes[*byte as usize] == 0 {
+                classes[*byte as usize] = stride as u16;
+                stride += 1;
+            }
+        }
+
+        // Build the trie. Its edges are stored in `transitions`, 0 meaning no
+        // edge since the root is never the target of an edge.
+        let mut transitions = vec![0u32; stride];
+        let mut outputs = vec![None; stride];
+        for (index, pattern) in patterns.into_iter().enumerate() {
+            let mut state = 0;
+            for byte in pattern {
+                let edge = state + classes[*byte as usize] as usize;
+                if transitions[edge] == 0 {
+                    transitions[edge] = transitions.len() as u32;
+                    transitions.resize(transitions.len() + stride, 0);
+                    outputs.resize(transitions.len(), None);
+                }
+                state = transitions[edge] as usize;
+            }
+            // Keep the first pattern if it is given twice.
+            outputs[state].get_or_insert((index, pattern.len()));
+        }
+
+        let patterns = outputs.clone();
+
+        // Add failure links with a breadth-first traversal, so that failure
+        // states are complete before the states relying on them.
+        let mut failures = vec![0; transitions.len()];
+        let mut queue = std::collections::VecDeque::from([0]);
+        while let Some(state) = queue.pop_front() {
+            for class in 0..stride {
+                let next = transitions[state + class] as usize;
+                if next == 0 {
+                    transitions[state + class] = transitions[failures[state] + class];
+                    continue;
+                }
+
+                failures[next] = if state == 0 {
+                    0
+                } else {
+                    transitions[failures[state] + class] as usize
+                };
+                // A pattern ending here is longer than any pattern ending in the
+                // failure state, which is a proper suffix.
+                if outputs[next].is_none() {
+                    outputs[next] = outputs[failures[next]];
+                }
+                queue.push_back(next);
+            }
+        }
+
+        Self {
+            classes,
+            transitions,
+            outputs,
+            patterns,
+            failures,
+        }
+    }
+
+    #[inline]
+    fn next_state(&self, state: usize, byte: u8) -> usize {
+        self.transitions[state + self.classes[byte as usize] as usize] as usize
+    }
+
+    /// Iterate over all patterns ending in `state`, from the longest to the shortest.
+    fn all_outputs(&self, state: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        std::iter::successors(Some(state), |state| {
+            (*state != 0).then(|| self.failures[*state])
+        })
+        .filter_map(|state| self.patterns[state])
+    }
+}
+
+/// Finds the first and last occurrences of a set of patterns in texts.
+#[derive(Debug, Clone)]
+pub struct Matcher<V> {
+    forward: Automaton,
+    backward: Automaton,
+    max_len: usize,
+    values: Vec<V>,
+    case_insensitive: bool,
+}
+
+/// Lowercase the chars of `text` whose lowercase form has the same UTF-8 length,
+/// so that byte offsets in the folded text are valid in the original one.
+fn fold_case(text: &str) -> Cow<'_, str> {
+    if !text.chars().any(char::is_uppercase) {
+        return Cow::Borrowed(text);
+    }
+
+    Cow::Owned(
+        text.chars()
+            .map(|c| {
+                let mut lowercase = c.to_lowercase();
+                match (lowercase.next(), lowercase.next()) {
+                    (Some(lower), None) if lower.len_utf8() == c.len_utf8() => lower,
+                    _ => c,
+                }
+            })
+            .collect(),
+    )
+}
+
+impl<V: Copy> Matcher<V> {
+    /// Build a matcher for the given patterns, each associated with a value.
+    ///
+    /// Empty patterns are ignored.
+    pub fn new<'a>(patterns: impl IntoIterator<Item = (&'a str, V)>) -> Self {
+        let (patterns, values): (Vec<&str>, Vec<V>) = patterns
+            .into_iter()
+            .filter(|(pattern, _)| !pattern.is_empty())
+            .unzip();
+
+        Self::from_patterns(&patterns, values, false)
+    }
+
+    /// Build a matcher ignoring case, for the given patterns each associated with a value.
+    ///
+    /// Empty patterns are ignored.
+    pub fn case_insensitive<'a>(patterns: impl IntoIterator<Item = (&'a str, V)>) -> Self {
+        let (patterns, values): (Vec<String>, Vec<V>) = patterns
+            .into_iter()
+            .filter(|(pattern, _)| !pattern.is_empty())
+            .map(|(pattern, value)| (fold_case(pattern).into_owned(), value))
+            .unzip();
+        let patterns: Vec<&str> = patterns.iter().map(String::as_str).collect();
+
+        Self::from_patterns(&patterns, values, true)
+    }
+
+    fn from_patterns(patterns: &[&str], values: Vec<V>, case_insensitive: bool) -> Self {
+        let reversed: Vec<Vec<u8>> = patterns
+            .iter()
+            .map(|pattern| pattern.bytes().rev().collect())
+            .collect();
+
+        Self {
+            forward: Automaton::new(patterns.iter().map(|pattern| pattern.as_bytes())),
+            backward: Automaton::new(reversed.iter().map(Vec::as_slice)),
+            max_len: patterns
+                .iter()
+                .map(|pattern| pattern.len())
+                .max()
+                .unwrap_or(0),
+            values,
+            case_insensitive,
+        }
+    }
+
+    /// Get the text to search in, case-folded if the matcher ignores case.
+    fn haystack<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        if self.case_insensitive {
+            fold_case(text)
+        } else {
+            Cow::Borrowed(text)
+        }
+    }
+
+    /// Find the occurrence starting the earliest in `haystack`. If several patterns
+    /// start at the same position, the longest one is returned.
+    pub fn find_first(&self, haystack: &str) -> Option<Match<V>> {
+        let haystack = self.haystack(haystack);
+        let bytes = haystack.as_bytes();
+        let mut best: Option<Match<V>> = None;
+        // No match ending at or after `limit` can start before the best one.
+        let mut limit = bytes.len();
+        let mut state = 0;
+
+        let mut i = 0;
+        while i < limit {
+            state = self.forward.next_state(state, bytes[i]);
+            if let Some((pattern, len)) = self.forward.outputs[state] {
+                let start = i + 1 - len;
+                if best
+                    .is_none_or(|best| start < best.start || (start == best.start && i >= best.end))
+                {
+                    best = Some(Match {
+                        start,
+                        end: i + 1,
+                        value: self.values[pattern],
+                    });
+                    limit = limit.min(start + self.max_len);
+                }
+            }
+            i += 1;
+        }
+
+        best
+    }
+
+    /// Find the occurrence starting the latest in `haystack`. If several patterns
+    /// start at the same position, the longest one is returned.
+    pub fn find_last(&self, haystack: &str) -> Option<Match<V>> {
+        // Scanning backwards, matches are found by decreasing start position.
+        let mut state = 0;
+        for (start, byte) in self.haystack(haystack).bytes().enumerate().rev() {
+            state = self.backward.next_state(state, byte);
+            if let Some((pattern, len)) = self.backward.outputs[state] {
+                return Some(Match {
+                    start,
+                    end: start + len,
+                    value: self.values[pattern],
+                });
+            }
+        }
+
+        None
+    }
+
+    /// Find all occurrences in `haystack`, overlapping ones included, ordered by
+    /// start position. If several patterns start at the same position, only the
+    /// longest one is returned.
+    pub fn find_all(&self, haystack: &str) -> Vec<Match<V>> {
+        let mut matches: Vec<(usize, usize, usize)> = Vec::new();
+        let mut state = 0;
+        for (i, byte) in self.haystack(haystack).bytes().enumerate() {
+            state = self.forward.next_state(state, byte);
+            matches.extend(
+                self.forward
+                    .all_outputs(state)
+                    .map(|(pattern, len)| (i + 1 - len, i + 1, pattern)),
+            );
+        }
+
+        // Sort by start, then longest first, and keep the first of each start.
+        matches.sort_by_key(|(start, end, _)| (*start, std::cmp::Reverse(*end)));
+        matches.dedup_by_key(|(start, _, _)| *start);
+        matches
+            .into_iter()
+            .map(|(start, end, pattern)| Match {
+                start,
+                end,
+                value: self.values[pattern],
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NUMBERS: [(&str, usize); 10] = [
+        ("one", 1),
+        ("two", 2),
+        ("three", 3),
+        ("eight", 8),
+        ("nine", 9),
+        ("1", 1),
+        ("2", 2),
+        ("3", 3),
+        ("8", 8),
+        ("9", 9),
+    ];
+
+    #[test]
+    fn find_first_and_last() {
+        let matcher = Matcher::new(NUMBERS);
+
+        assert_eq!(
+            matcher.find_first("xtwone3four"),
+            Some(Match {
+                start: 1,
+                end: 4,
+                value: 2
+            })
+        );
+        assert_eq!(
+            matcher.find_last("xtwone3four"),
+            Some(Match {
+                start: 6,
+                end: 7,
+                value: 3
+            })
+        );
+        assert_eq!(matcher.find_first("abcdef"), None);
+        assert_eq!(matcher.find_last(""), None);
+    }
+
+    #[test]
+    fn overlapping_words() {
+        let matcher = Matcher::new(NUMBERS);
+
+        assert_eq!(matcher.find_first("eightwo").map(|m| m.value), Some(8));
+        assert_eq!(matcher.find_last("eightwo").map(|m| m.value), Some(2));
+        assert_eq!(matcher.find_first("oneight").map(|m| m.value), Some(1));
+        assert_eq!(matcher.find_last("oneight").map(|m| m.value), Some(8));
+        assert_eq!(matcher.find_last("twoneighthree").map(|m| m.value), Some(3));
+    }
+
+    #[test]
+    fn leftmost_start_wins_over_earliest_end() {
+        let matcher = Matcher::new([("abcd", 1), ("bc", 2), ("cdef", 3), ("d", 4)]);
+
+        assert_eq!(
+            matcher.find_first("xabcdef"),
+            Some(Match {
+                start: 1,
+                end: 5,
+                value: 1
+            })
+        );
+        assert_eq!(
+            matcher.find_last("xabcdef"),
+            Some(Match {
+                start: 4,
+                end: 5,
+                value: 4
+            })
+        );
+    }
+
+    #[test]
+    fn longest_pattern_on_same_start() {
+        let matcher = Matcher::new([("se", 1), ("seven", 7), ("s", 0)]);
+
+        assert_eq!(matcher.find_first("xsevenx").map(|m| m.value), Some(7));
+        assert_eq!(matcher.find_last("xsevenx").map(|m| m.value), Some(7));
+    }
+
+    #[test]
+    fn find_all_overlapping() {
+        let matcher = Matcher::new(NUMBERS);
+        let values = |haystack| {
+            matcher
+                .find_all(haystack)
+                .iter()
+                .map(|m| m.value)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(values("twoneightwo3"), vec![2, 1, 8, 2, 3]);
+        assert_eq!(values("xnineightx"), vec![9, 8]);
+        assert_eq!(values("abc"), vec![]);
+        assert_eq!(
+            Matcher::new([("seven", 7), ("se", 1), ("even", 2), ("n", 3)]).find_all("seven"),
+            vec![
+                Match {
+                    start: 0,
+                    end: 5,
+                    value: 7
+                },
+                Match {
+                    start: 1,
+                    end: 5,
+                    value: 2
+                },
+                Match {
+                    start: 4,
+                    end: 5,
+                    value: 3
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn case_insensitive_matching() {
+        let matcher = Matcher::case_insensitive([("zéro", 0), ("Fünf", 5), ("eight", 8)]);
+
+        assert_eq!(matcher.find_first("xEiGhT").map(|m| m.start), Some(1));
+        assert_eq!(matcher.find_last("ZÉROxFÜNFx").map(|m| m.value), Some(5));
+        assert_eq!(matcher.find_first("ZÉROxFÜNFx").map(|m| m.value), Some(0));
+        // 'İ' lowercases to two chars, it is kept as is so offsets stay valid.
+        assert_eq!(
+            matcher.find_first("İEIGHT"),
+            Some(Match {
+                start: 2,
+                end: 7,
+                value: 8
+            })
+        );
+        assert_eq!(Matcher::new([("eight", 8)]).find_first("EIGHT"), None);
+    }
+
+    #[test]
+    fn multi_byte_text() {
+        let matcher = Matcher::new([("é", 1), ("deux", 2)]);
+
+        assert_eq!(
+            matcher.find_first("ndeuxé"),
+            Some(Match {
+                start: 1,
+                end: 5,
+                value: 2
+            })
+        );
+        assert_eq!(
+            matcher.find_last("ndeuxé"),
+            Some(Match {
+                start: 5,
+                end: 7,
+                value: 1
+            })
+        );
+    }
+}
@@ -0,0 +1,295 @@
+//! Recognition of compound English numerals, such as "nineteen", "twenty-one" or
+//! "one hundred and five".
+use crate::dictionary::MatchOptions;
+use crate::digits::unicode_digits;
+use crate::matcher::{Match, Matcher};
+
+const SMALL: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+const TENS: [&str; 8] = [
+    "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+const SCALES: [(&str, usize); 2] = [("thousand", 1_000), ("million", 1_000_000)];
+
+/// Word of a numeral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Word {
+    /// Number from zero to nineteen.
+    Small(usize),
+    /// Multiple of ten from twenty to ninety.
+    Tens(usize),
+    Hundred,
+    /// Thousand or million.
+    Scale(usize),
+    And,
+    /// Digit written as such, always a number on its own.
+    Digit(usize),
+}
+
+/// How number words sharing letters, like "eightwo", are read.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Overlap {
+    /// Words may share letters, "eightwo" is eight then two, as in the puzzle.
+    #[default]
+    Share,
+    /// Words are read from left to right without sharing letters, "eightwo" is
+    /// eight, the remaining "wo" is ignored.
+    Exclusive,
+}
+
+/// Numeral found in a text, with its byte range.
+pub type Numeral = Match<usize>;
+
+/// Value of a numeral being read, word by word.
+#[derive(Debug, Default)]
+struct NumeralValue {
+    /// Sum of the groups already multiplied by a scale.
+    total: usize,
+    /// Group below one thousand being read.
+    group: usize,
+    /// Smallest scale used so far, the next one must be smaller.
+    scale: Option<usize>,
+    previous: Option<Word>,
+}
+
+impl NumeralValue {
+    /// Add `word` to the numeral, returning false if it cannot extend it.
+    fn push(&mut self, word: Word) -> bool {
+        use Word::*;
+
+        let accepted = match (self.previous, word) {
+            (_, Digit(_)) | (Some(Digit(_) | Small(0)), _) => false,
+            (None, Small(_) | Tens(_)) => true,
+            (Some(Tens(_)), Small(value)) => value != 0,
+            (Some(Hundred | Scale(_) | And), Small(value)) => value != 0,
+            (Some(Hundred | Scale(_) | And), Tens(_)) => true,
+            (Some(Small(value)), Hundred) => value != 0 && self.group == value,
+            (Some(Small(_) | Tens(_) | Hundred), Scale(scale)) => {
+                self.scale.is_none_or(|smallest| scale < smallest)
+            }
+            (Some(Hundred | Scale(_)), And) => true,
+            _ => false,
+        };
+        if !accepted {
+            return false;
+        }
+
+        match word {
+            Small(value) | Tens(value) => self.group += value,
+            Hundred => self.group *= 100,
+            Scale(scale) => {
+                self.total += self.group * scale;
+                self.group = 0;
+                self.scale = Some(scale);
+            }
+            And | Digit(_) => (),
+        }
+        self.previous = Some(word);
+        true
+    }
+
+    fn value(&self) -> usize {
+        self.total + self.group
+    }
+}
+
+/// Finds compound English numerals and digits in texts.
+#[derive(Debug, Clone)]
+pub struct NumeralMatcher {
+    matcher: Matcher<Word>,
+    overlap: Overlap,
+}
+
+impl NumeralMatcher {
+    /// Build a matcher recognizing English numerals and digits, each digit being
+    /// a number on its own.
+    pub fn new(options: MatchOptions, overlap: Overlap) -> Self {
+        let digits: Vec<(String, usize)> = unicode_digits()
+            .filter(|(c, _)| options.unicode_digits || c.is_ascii_digit())
+            .map(|(c, digit)| (c.to_string(), digit))
+            .collect();
+        let patterns = SMALL
+            .iter()
+            .enumerate()
+            .map(|(value, word)| (*word, Word::Small(value)))
+            .chain(
+                TENS.iter()
+                    .enumerate()
+                    .map(|(i, word)| (*word, Word::Tens(20 + 10 * i))),
+            )
+            .chain([("hundred", Word::Hundred), ("and", Word::And)])
+            .chain(
+                SCALES
+                    .iter()
+                    .map(|(word, scale)| (*word, Word::Scale(*scale))),
+            )
+            .chain(
+                digits
+                    .iter()
+                    .map(|(digit_str, digit)| (digit_str.as_str(), Word::Digit(*digit))),
+            );
+
+        let matcher = if options.case_insensitive {
+            Matcher::case_insensitive(patterns)
+        } else {
+            Matcher::new(patterns)
+        };
+
+        Self { matcher, overlap }
+    }
+
+    /// Find the words of `haystack`, keeping only the ones allowed by the overlap
+    /// policy. Words contained in a previous one, like "and" in "thousand", are
+    /// always dropped.
+    fn words(&self, haystack: &str) -> Vec<Match<Word>> {
+        let mut words: Vec<Match<Word>> = Vec::new();
+        for word in self.matcher.find_all(haystack) {
+            let allowed = words.last().is_none_or(|previous| match self.overlap {
+                Overlap::Share => word.end > previous.end,
+                Overlap::Exclusive => word.start >= previous.end,
+            });
+            if allowed {
+                words.push(word);
+            }
+        }
+        words
+    }
+
+    /// Find all numerals in `haystack`, in reading order.
+    ///
+    /// Words of a numeral are separated by nothing, a space or a hyphen. A word
+    /// that cannot continue the current numeral, or that shares letters with it,
+    /// starts a new one. A trailing "and" is not part of the numeral.
+    pub fn find_all(&self, haystack: &str) -> Vec<Numeral> {
+        let mut numerals = Vec::new();
+        // Numeral being read, with its value and the end of its last word.
+        let mut current: Option<(Numeral, NumeralValue, usize)> = None;
+
+        for word in self.words(haystack) {
+            if let Some((numeral, value, last_end)) = current.as_mut() {
+                let gap = haystack.get(*last_end..word.start);
+                if matches!(gap, Some("" | " " | "-")) && value.push(word.value) {
+                    *last_end = word.end;
+                    if word.value != Word::And {
+                        numeral.end = word.end;
+                        numeral.value = value.value();
+                    }
+                    continue;
+                }
+                numerals.extend(current.take().map(|(numeral, _, _)| numeral));
+            }
+
+            let numeral = |value| Numeral {
+                start: word.start,
+                end: word.end,
+                value,
+            };
+            let mut value = NumeralValue::default();
+            match word.value {
+                // A digit cannot be continued.
+                Word::Digit(digit) => numerals.push(numeral(digit)),
+                _ if value.push(word.value) => {
+                    current = Some((numeral(value.value()), value, word.end));
+                }
+                _ => (),
+            }
+        }
+        numerals.extend(current.map(|(numeral, _, _)| numeral));
+
+        numerals
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(matcher: &NumeralMatcher, haystack: &str) -> Vec<usize> {
+        matcher
+            .find_all(haystack)
+            .iter()
+            .map(|numeral| numeral.value)
+            .collect()
+    }
+
+    #[test]
+    fn compound_numerals() {
+        let matcher = NumeralMatcher::new(MatchOptions::default(), Overlap::Share);
+
+        assert_eq!(values(&matcher, "xnineteen"), vec![19]);
+        assert_eq!(values(&matcher, "twenty-one4"), vec![21, 4]);
+        assert_eq!(values(&matcher, "one hundred and five"), vec![105]);
+        assert_eq!(values(&matcher, "onehundredfive"), vec![105]);
+        assert_eq!(
+            values(&matcher, "two thousand three hundred and forty-five"),
+            vec![2345]
+        );
+        assert_eq!(values(&matcher, "seventy seven"), vec![77]);
+        assert_eq!(values(&matcher, "seven seventy"), vec![7, 70]);
+        assert_eq!(values(&matcher, "ten five"), vec![10, 5]);
+        assert_eq!(values(&matcher, "zero one"), vec![0, 1]);
+        assert_eq!(values(&matcher, "five hundred and bob"), vec![500]);
+        assert_eq!(values(&matcher, "thousand band"), vec![]);
+        assert_eq!(values(&matcher, "twenty, one"), vec![20, 1]);
+        assert_eq!(values(&matcher, "42"), vec![4, 2]);
+    }
+
+    #[test]
+    fn numeral_ranges() {
+        let matcher = NumeralMatcher::new(MatchOptions::default(), Overlap::Share);
+
+        assert_eq!(
+            matcher.find_all("é twenty-one and"),
+            vec![Numeral {
+                start: 3,
+                end: 13,
+                value: 21
+            }]
+        );
+    }
+
+    #[test]
+    fn overlapping_words() {
+        let share = NumeralMatcher::new(MatchOptions::default(), Overlap::Share);
+        let exclusive = NumeralMatcher::new(MatchOptions::default(), Overlap::Exclusive);
+
+        assert_eq!(values(&share, "eightwo"), vec![8, 2]);
+        assert_eq!(values(&exclusive, "eightwo"), vec![8]);
+        assert_eq!(values(&share, "twentyoneight"), vec![21, 8]);
+        assert_eq!(values(&exclusive, "twentyoneight"), vec![21]);
+        assert_eq!(values(&share, "eighteen"), vec![18]);
+        assert_eq!(values(&exclusive, "sevenineteen"), vec![7]);
+        assert_eq!(values(&share, "sevenineteen"), vec![7, 19]);
+    }
+
+    #[test]
+    fn case_insensitive_numerals() {
+        let options = MatchOptions {
+            unicode_digits: true,
+            case_insensitive: true,
+        };
+        let matcher = NumeralMatcher::new(options, Overlap::Share);
+
+        assert_eq!(values(&matcher, "Forty-Two٣"), vec![42, 3]);
+    }
+}
@@ -0,0 +1,158 @@
+//! Policies combining the numbers found on a line into its calibration value.
+use std::fmt;
+use std::str::FromStr;
+
+use aoc_lib::{Error, Result};
+
+/// How the numbers of a line, in reading order, make its calibration value.
+///
+/// Numbers are usually digits. Larger ones, read from compound numerals, are
+/// concatenated with all their digits: first 21 and last 5 make 215.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DigitPolicy {
+    /// First number followed by the last one, as in the puzzle.
+    #[default]
+    FirstLast,
+    /// All numbers concatenated.
+    Concatenate,
+    /// First N numbers concatenated, or all of them if there are fewer.
+    First(usize),
+    /// Last N numbers concatenated, or all of them if there are fewer.
+    Last(usize),
+    /// Sum of all numbers.
+    Sum,
+}
+
+/// Concatenate the decimal representations of numbers.
+fn concatenate(numbers: &[usize]) -> Result<usize> {
+    numbers.iter().try_fold(0usize, |concatenation, number| {
+        let shift = 10usize.checked_pow(number.checked_ilog10().unwrap_or(0) + 1);
+        shift
+            .and_then(|shift| concatenation.checked_mul(shift))
+            .and_then(|concatenation| concatenation.checked_add(*number))
+            .ok_or_else(|| {
+                Error::OverflowError(format!("concatenating {numbers:?} overflows a usize"))
+            })
+    })
+}
+
+impl DigitPolicy {
+    /// Combine `numbers`, returning `None` if there are none.
+    pub fn apply(&self, numbers: &[usize]) -> Result<Option<usize>> {
+        let (Some(first), Some(last)) = (numbers.first(), numbers.last()) else {
+            return Ok(None);
+        };
+
+        let value = match self {
+            DigitPolicy::FirstLast => concatenate(&[*first, *last])?,
+            DigitPolicy::Concatenate => concatenate(numbers)?,
+            DigitPolicy::First(n) => concatenate(&numbers[..numbers.len().min(*n)])?,
+            DigitPolicy::Last(n) => concatenate(&numbers[numbers.len().saturating_sub(*n)..])?,
+            DigitPolicy::Sum => numbers
+                .iter()
+                .try_fold(0usize, |sum, number| sum.checked_add(*number))
+                .ok_or_else(|| {
+                    Error::OverflowError(format!("summing {numbers:?} overflows a usize"))
+                })?,
+        };
+
+        Ok(Some(value))
+    }
+}
+
+/// Parse a policy: `first-last`, `concat`, `first:N`, `last:N` or `sum`.
+impl FromStr for DigitPolicy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let count = |n: &str| match n.parse() {
+            Ok(0) | Err(_) => Err(Error::ParseError(format!(
+                "'{n}' should be a positive number of digits"
+            ))),
+            Ok(n) => Ok(n),
+        };
+
+        match s.split_once(':') {
+            None if s == "first-last" => Ok(DigitPolicy::FirstLast),
+            None if s == "concat" => Ok(DigitPolicy::Concatenate),
+            None if s == "sum" => Ok(DigitPolicy::Sum),
+            Some(("first", n)) => Ok(DigitPolicy::First(count(n)?)),
+            Some(("last", n)) => Ok(DigitPolicy::Last(count(n)?)),
+            _ => Err(Error::ParseError(format!(
+                "unknown digit policy '{s}', expected first-last, concat, first:N, last:N or sum"
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for DigitPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DigitPolicy::FirstLast => write!(f, "first-last"),
+            DigitPolicy::Concatenate => write!(f, "concat"),
+            DigitPolicy::First(n) => write!(f, "first:{n}"),
+            DigitPolicy::Last(n) => write!(f, "last:{n}"),
+            DigitPolicy::Sum => write!(f, "sum"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_policies() -> Result<()> {
+        let digits = [4, 0, 7, 2];
+
+        assert_eq!(DigitPolicy::FirstLast.apply(&digits)?, Some(42));
+        assert_eq!(DigitPolicy::FirstLast.apply(&[7])?, Some(77));
+        assert_eq!(DigitPolicy::Concatenate.apply(&digits)?, Some(4072));
+        assert_eq!(DigitPolicy::First(2).apply(&digits)?, Some(40));
+        assert_eq!(DigitPolicy::Last(3).apply(&digits)?, Some(72));
+        assert_eq!(DigitPolicy::Last(9).apply(&digits)?, Some(4072));
+        assert_eq!(DigitPolicy::Sum.apply(&digits)?, Some(13));
+        assert_eq!(DigitPolicy::Sum.apply(&[])?, None);
+        assert_eq!(DigitPolicy::FirstLast.apply(&[0, 5])?, Some(5));
+
+        Ok(())
+    }
+
+    #[test]
+    fn apply_to_larger_numbers() -> Result<()> {
+        let numbers = [21, 0, 105];
+
+        assert_eq!(DigitPolicy::FirstLast.apply(&numbers)?, Some(21105));
+        assert_eq!(DigitPolicy::Concatenate.apply(&numbers)?, Some(210105));
+        assert_eq!(DigitPolicy::Last(2).apply(&numbers)?, Some(105));
+        assert_eq!(DigitPolicy::Sum.apply(&numbers)?, Some(126));
+
+        Ok(())
+    }
+
+    #[test]
+    fn concatenation_overflow() {
+        assert!(matches!(
+            DigitPolicy::Concatenate.apply(&[9; 30]),
+            Err(Error::OverflowError(_))
+        ));
+    }
+
+    #[test]
+    fn parse_policies() -> Result<()> {
+        for policy in [
+            DigitPolicy::FirstLast,
+            DigitPolicy::Concatenate,
+            DigitPolicy::First(3),
+            DigitPolicy::Last(1),
+            DigitPolicy::Sum,
+        ] {
+            assert_eq!(policy.to_string().parse::<DigitPolicy>()?, policy);
+        }
+        assert!("first:0".parse::<DigitPolicy>().is_err());
+        assert!("last:x".parse::<DigitPolicy>().is_err());
+        assert!("middle".parse::<DigitPolicy>().is_err());
+
+        Ok(())
+    }
+}
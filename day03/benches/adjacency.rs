@@ -0,0 +1,91 @@
+use aoc_lib::answer::Answer;
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use day03::{
+    neighbourhood::Neighbourhood,
+    schematic::{Number, PartCounting, Symbol, parse_input, part_1},
+};
+
+/// The scan of all numbers of the three lines around each symbol, used before
+/// the binary search, as a baseline.
+fn linear(numbers: &[Vec<Number>], symbols: &[Symbol]) -> Answer {
+    symbols
+        .iter()
+        .map(|symbol| {
+            let (line, column) = symbol.position;
+            (line.saturating_sub(1)..=line + 1)
+                .flat_map(|line_number| numbers.get(line_number))
+                .flatten()
+                .filter(|number| number.is_adjacent_to(0, column, Neighbourhood::Chebyshev(1)))
+                .map(|number| number.value as Answer)
+                .sum::<Answer>()
+        })
+        .sum()
+}
+
+/// Generate a schematic of `lines` lines of `width` cells, with about one
+/// number every 8 cells and one symbol every 16 cells.
+fn schematic(lines: usize, width: usize) -> String {
+    let mut seed: u64 = 0x2023_0003;
+    let mut next = move || {
+        seed = seed
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (seed >> 33) as usize
+    };
+
+    let mut schematic = String::new();
+    for _ in 0..lines {
+        let mut column = 0;
+        while column < width {
+            match next() % 16 {
+                0 | 1 => {
+                    let digits = (1 + next() % 3).min(width - column);
+                    for _ in 0..digits {
+                        schematic.push(char::from(b'0' + (next() % 10) as u8));
+                    }
+                    column += digits;
+                }
+                2 => {
+                    schematic.push(['*', '#', '+', '$'][next() % 4]);
+                    column += 1;
+                }
+                _ => {
+                    schematic.push('.');
+                    column += 1;
+                }
+            }
+        }
+        schematic.push('\n');
+    }
+    schematic
+}
+
+fn bench_adjacency(c: &mut Criterion) {
+    let mut group = c.benchmark_group("adjacency");
+    group.sample_size(10);
+
+    for width in [140, 1_000, 10_000] {
+        let input = schematic(100, width);
+        let (numbers, symbols) = parse_input(&input).expect("schematic should parse");
+        group.throughput(Throughput::Bytes(input.len() as u64));
+
+        group.bench_function(BenchmarkId::new("linear", width), |b| {
+            b.iter(|| linear(&numbers, &symbols))
+        });
+        group.bench_function(BenchmarkId::new("binary_search", width), |b| {
+            b.iter(|| {
+                part_1(
+                    &numbers,
+                    &symbols,
+                    PartCounting::PerSymbol,
+                    Neighbourhood::default(),
+                )
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_adjacency);
+criterion_main!(benches);
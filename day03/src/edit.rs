@@ -0,0 +1,505 @@
+//! Schematic edited cell by cell, keeping the sums of parts 1 and 2 up to date.
+//!
+//! Changing a cell can only change the numbers of its line touching it, so only
+//! that line is parsed again. The sums then only change by the contributions of
+//! the symbols and numbers around these numbers, which are removed before the
+//! change and added back after it.
+//!
+//! Cells can only be changed, not added: lines keep the length they had in the
+//! input.
+use std::fmt;
+
+use aoc_lib::{
+    Error, Result,
+    answer::{CheckedIterator, TryCheckedIterator},
+    math::Integer,
+};
+
+use crate::{
+    gear::GearSpec,
+    neighbourhood::Neighbourhood,
+    schematic::{
+        Minus, Number, PartCounting, Symbol, Value, compute_gear_ratio, parse, part_1, part_2,
+        sum_numbers_around_symbol,
+    },
+};
+
+/// Schematic whose cells can be changed, with the sums of parts 1 and 2.
+#[derive(Debug, Clone)]
+pub struct EditableSchematic<V: Value = usize> {
+    cells: Vec<Vec<char>>,
+    numbers: Vec<Vec<Number<V>>>,
+    /// Symbols, sorted by position.
+    symbols: Vec<Symbol>,
+    counting: PartCounting,
+    gear: GearSpec,
+    neighbourhood: Neighbourhood,
+    /// How a '-' directly before digits is read.
+    minus: Minus,
+    part_1: V::Total,
+    part_2: V::Total,
+}
+
+/// Lines and columns around a change whose symbols and numbers are re-evaluated.
+struct Region {
+    lines: std::ops::RangeInclusive<usize>,
+    columns: std::ops::RangeInclusive<usize>,
+}
+
+impl EditableSchematic {
+    pub fn new(
+        input: &str,
+        counting: PartCounting,
+        gear: GearSpec,
+        neighbourhood: Neighbourhood,
+    ) -> Result<Self> {
+        Self::with_minus(input, counting, gear, neighbourhood, Minus::Symbol)
+    }
+}
+
+impl EditableSchematic<isize> {
+    /// Schematic reading a '-' directly before digits as set by `minus`, as
+    /// [`parse_signed_input`](crate::schematic::parse_signed_input).
+    pub fn signed(
+        input: &str,
+        counting: PartCounting,
+        gear: GearSpec,
+        neighbourhood: Neighbourhood,
+        minus: Minus,
+    ) -> Result<Self> {
+        Self::with_minus(input, counting, gear, neighbourhood, minus)
+    }
+}
+
+impl<V: Value> EditableSchematic<V> {
+    fn with_minus(
+        input: &str,
+        counting: PartCounting,
+        gear: GearSpec,
+        neighbourhood: Neighbourhood,
+        minus: Minus,
+    ) -> Result<Self> {
+        let (numbers, symbols) = parse(input, minus)?;
+        let mut schematic = Self {
+            cells: input.lines().map(|line| line.chars().collect()).collect(),
+            numbers,
+            symbols,
+            counting,
+            gear,
+            neighbourhood,
+            minus,
+            part_1: V::Total::ZERO,
+            part_2: V::Total::ZERO,
+        };
+        schematic.compute_totals()?;
+
+        Ok(schematic)
+    }
+
+    /// Sums of parts 1 and 2.
+    pub fn totals(&self) -> (V::Total, V::Total) {
+        (self.part_1, self.part_2)
+    }
+
+    pub fn numbers(&self) -> &[Vec<Number<V>>] {
+        &self.numbers
+    }
+
+    /// All symbols, sorted by position.
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+
+    pub fn counting(&self) -> PartCounting {
+        self.counting
+    }
+
+    pub fn gear(&self) -> &GearSpec {
+        &self.gear
+    }
+
+    pub fn neighbourhood(&self) -> Neighbourhood {
+        self.neighbourhood
+    }
+
+    /// Change the gear rules, and compute the sums again.
+    pub fn set_gear(&mut self, gear: GearSpec) -> Result<(V::Total, V::Total)> {
+        self.gear = gear;
+        self.compute_totals()
+    }
+
+    /// Change the neighbourhood, and compute the sums again.
+    pub fn set_neighbourhood(
+        &mut self,
+        neighbourhood: Neighbourhood,
+    ) -> Result<(V::Total, V::Total)> {
+        self.neighbourhood = neighbourhood;
+        self.compute_totals()
+    }
+
+    /// Set the cell at `(line, column)` to a digit, a symbol or '.', and
+    /// return the new sums of parts 1 and 2. The cell must exist: lines cannot
+    /// grow.
+    pub fn set(&mut self, (line, column): (usize, usize), c: char) -> Result<(V::Total, V::Total)> {
+        if c == '\n' || c == '\r' {
+            return Err(Error::ParseError(
+                "a cell cannot hold a line break".to_string(),
+            ));
+        }
+        let line_count = self.cells.len();
+        let cells = self.cells.get(line).ok_or_else(|| {
+            Error::ParseError(format!("no line {line}, the schematic has {line_count}"))
+        })?;
+        let width = cells.len();
+        let cell = *cells.get(column).ok_or_else(|| {
+            Error::ParseError(format!(
+                "no column {column} on line {line} of {width} cells, lines cannot grow"
+            ))
+        })?;
+        if cell == c {
+            return Ok(self.totals());
+        }
+
+        let text: String = cells
+            .iter()
+            .enumerate()
+            .map(|(i, &cell)| if i == column { c } else { cell })
+            .collect();
+        let (mut numbers, symbols) = parse(&text, self.minus)?;
+        let numbers = numbers.pop().unwrap_or_default();
+        let symbols: Vec<Symbol> = symbols
+            .into_iter()
+            .map(|symbol| Symbol {
+                position: (line, symbol.position.1),
+                sym: symbol.sym,
+            })
+            .collect();
+
+        let region = self.region(line, column, &numbers);
+        let (old_part_1, old_part_2) = self.contribution(&region)?;
+
+        // Evaluate the new line in place, and put the old one back if it fails.
+        let old_numbers = std::mem::replace(&mut self.numbers[line], numbers);
+        let line_symbols = self.line_symbols(line..=line);
+        let old_symbols: Vec<Symbol> = self.symbols.splice(line_symbols, symbols).collect();
+        let totals = self
+            .contribution(&region)
+            .and_then(|(new_part_1, new_part_2)| {
+                Ok((
+                    replace(self.part_1, old_part_1, new_part_1, "sum of part numbers")?,
+                    replace(self.part_2, old_part_2, new_part_2, "sum of gear ratios")?,
+                ))
+            });
+        match totals {
+            Ok((part_1, part_2)) => {
+                self.cells[line][column] = c;
+                (self.part_1, self.part_2) = (part_1, part_2);
+                Ok(self.totals())
+            }
+            Err(err) => {
+                self.numbers[line] = old_numbers;
+                let line_symbols = self.line_symbols(line..=line);
+                self.symbols.splice(line_symbols, old_symbols);
+                Err(err)
+            }
+        }
+    }
+
+    /// Clear the cell at `(line, column)`, and return the new sums.
+    pub fn clear(&mut self, position: (usize, usize)) -> Result<(V::Total, V::Total)> {
+        self.set(position, '.')
+    }
+
+    /// Compute the sums of parts 1 and 2 from scratch.
+    fn compute_totals(&mut self) -> Result<(V::Total, V::Total)> {
+        self.part_1 = part_1(
+            &self.numbers,
+            &self.symbols,
+            self.counting,
+            self.neighbourhood,
+        )?;
+        self.part_2 = part_2(&self.numbers, &self.symbols, &self.gear, self.neighbourhood)?;
+
+        Ok(self.totals())
+    }
+
+    /// Indices of the symbols of the given lines.
+    fn line_symbols(&self, lines: std::ops::RangeInclusive<usize>) -> std::ops::Range<usize> {
+        let start = self
+            .symbols
+            .partition_point(|symbol| symbol.position.0 < *lines.start());
+        let end = start
+            + self.symbols[start..].partition_point(|symbol| symbol.position.0 <= *lines.end());
+        start..end
+    }
+
+    /// Find the region whose sums can change when the numbers of `line` become
+    /// `new_numbers` after a change in `column`: the neighbourhood of the
+    /// changed cell and of the numbers which appear or disappear.
+    fn region(&self, line: usize, column: usize, new_numbers: &[Number<V>]) -> Region {
+        let old_numbers = &self.numbers[line];
+        let changed = old_numbers
+            .iter()
+            .filter(|number| !new_numbers.contains(number))
+            .chain(
+                new_numbers
+                    .iter()
+                    .filter(|number| !old_numbers.contains(number)),
+            );
+        let (start, end) = changed.fold((column, column), |(start, end), number| {
+            (
+                start.min(number.position),
+                end.max(number.position + number.len - 1),
+            )
+        });
+
+        let radius = self.neighbourhood.radius();
+        let reach = self.neighbourhood.column_reach(0).unwrap_or(0);
+        Region {
+            lines: line.saturating_sub(radius)..=line.saturating_add(radius),
+            columns: start.saturating_sub(reach)..=end.saturating_add(reach),
+        }
+    }
+
+    /// Sums of parts 1 and 2 coming from the region: the numbers around its
+    /// symbols, or its part numbers when each is counted once, and the ratios of
+    /// its gears.
+    fn contribution(&self, region: &Region) -> Result<(V::Total, V::Total)> {
+        let symbols: Vec<&Symbol> = self.symbols[self.line_symbols(region.lines.clone())]
+            .iter()
+            .filter(|symbol| region.columns.contains(&symbol.position.1))
+            .collect();
+
+        let part_1 = match self.counting {
+            PartCounting::PerSymbol => symbols
+                .iter()
+                .map(|symbol| sum_numbers_around_symbol(&self.numbers, symbol, self.neighbourhood))
+                .try_checked_sum("sum of part numbers")?,
+            PartCounting::Unique => region
+                .lines
+                .clone()
+                .filter_map(|line| self.numbers.get(line).map(|numbers| (line, numbers)))
+                .flat_map(|(line, numbers)| numbers.iter().map(move |number| (line, number)))
+                .filter(|(_, number)| {
+                    number.position <= *region.columns.end()
+                        && number.position + number.len > *region.columns.start()
+                })
+                .filter(|(line, number)| self.is_part(*line, number))
+                .map(|(_, number)| number.value.total())
+                .checked_sum("sum of part numbers")?,
+        };
+        let part_2 = symbols
+            .iter()
+            .filter_map(|symbol| {
+                compute_gear_ratio(&self.numbers, symbol, &self.gear, self.neighbourhood)
+            })
+            .try_checked_sum("sum of gear ratios")?;
+
+        Ok((part_1, part_2))
+    }
+
+    /// Check if a number of `line` is adjacent to a symbol.
+    fn is_part(&self, line: usize, number: &Number<V>) -> bool {
+        let radius = self.neighbourhood.radius();
+        let lines = line.saturating_sub(radius)..=line.saturating_add(radius);
+        self.symbols[self.line_symbols(lines)].iter().any(|symbol| {
+            number.is_adjacent_to(
+                line.abs_diff(symbol.position.0),
+                symbol.position.1,
+                self.neighbourhood,
+            )
+        })
+    }
+}
+
+/// Replace the contribution `old` to `total` by `new`.
+fn replace<T: Integer>(total: T, old: T, new: T, what: &str) -> Result<T> {
+    total
+        .checked_sub(old)
+        .and_then(|total| total.checked_add(new))
+        .ok_or_else(|| Error::OverflowError(format!("{what}: {total:?} - {old:?} + {new:?}")))
+}
+
+/// Print the schematic as text, line by line.
+impl<V: Value> fmt::Display for EditableSchematic<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in &self.cells {
+            writeln!(f, "{}", line.iter().collect::<String>())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Check the sums and parsing of `schematic` against a parse from scratch.
+    fn assert_matches_batch<V: Value>(schematic: &EditableSchematic<V>) -> Result<()> {
+        let text = schematic.to_string();
+        let (numbers, symbols) = parse(&text, schematic.minus)?;
+
+        assert_eq!(schematic.numbers(), numbers, "{text}");
+        assert_eq!(schematic.symbols(), symbols, "{text}");
+        assert_eq!(
+            schematic.totals(),
+            (
+                part_1(
+                    &numbers,
+                    &symbols,
+                    schematic.counting,
+                    schematic.neighbourhood
+                )?,
+                part_2(&numbers, &symbols, &schematic.gear, schematic.neighbourhood)?
+            ),
+            "{text}"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn edit_sample() -> Result<()> {
+        let mut schematic = EditableSchematic::new(
+            include_str!("../../inputs/tests/03.txt"),
+            PartCounting::PerSymbol,
+            GearSpec::default(),
+            Neighbourhood::default(),
+        )?;
+        assert_eq!(schematic.totals(), (4361, 467835));
+
+        // 114 becomes a part number, and 35 is counted for a second symbol.
+        assert_eq!(schematic.set((1, 4), '#')?, (4361 + 114 + 35, 467835));
+        // 467 becomes 46, too far from the first gear.
+        assert_eq!(
+            schematic.clear((0, 2))?,
+            (4361 + 114 + 35 - 467, 467835 - 467 * 35)
+        );
+        assert_eq!(schematic.set((0, 2), '7')?, (4361 + 114 + 35, 467835));
+        assert_eq!(schematic.set((0, 2), '7')?, (4361 + 114 + 35, 467835));
+        assert_matches_batch(&schematic)?;
+
+        assert!(schematic.set((10, 0), '1').is_err());
+        assert!(schematic.set((0, 10), '1').is_err());
+        assert!(schematic.set((0, 1), '\n').is_err());
+
+        // Every '*' and '#' is now a gear summing its numbers.
+        assert_eq!(
+            schematic.set_gear("*#:>=1:sum".parse()?)?.1,
+            (467 + 35) + (114 + 35) + 633 + 617 + (755 + 598)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn failed_edits_change_nothing() -> Result<()> {
+        // The new number overflows a usize.
+        let mut schematic = EditableSchematic::new(
+            "1844674407370955161.\n*...",
+            PartCounting::PerSymbol,
+            GearSpec::default(),
+            Neighbourhood::default(),
+        )?;
+        let (text, numbers, totals) = (
+            schematic.to_string(),
+            schematic.numbers().to_vec(),
+            schematic.totals(),
+        );
+        assert!(schematic.set((0, 19), '9').is_err());
+        assert_eq!(schematic.to_string(), text);
+        assert_eq!(schematic.numbers(), numbers);
+        assert_eq!(schematic.totals(), totals);
+        assert_matches_batch(&schematic)?;
+
+        // A third number makes the gear ratio overflow.
+        let max = usize::MAX;
+        let mut schematic = EditableSchematic::new(
+            &format!("{max}\n*.\n{max}"),
+            PartCounting::PerSymbol,
+            "*:>=2:product".parse()?,
+            Neighbourhood::default(),
+        )?;
+        let (text, numbers, totals) = (
+            schematic.to_string(),
+            schematic.numbers().to_vec(),
+            schematic.totals(),
+        );
+        assert!(matches!(
+            schematic.set((1, 1), '9'),
+            Err(Error::OverflowError(_))
+        ));
+        assert_eq!(schematic.to_string(), text);
+        assert_eq!(schematic.numbers(), numbers);
+        assert_eq!(schematic.totals(), totals);
+        assert_matches_batch(&schematic)?;
+
+        Ok(())
+    }
+
+    /// Pseudo-random generator, reproducible from its seed.
+    fn generator(mut seed: u64) -> impl FnMut() -> usize {
+        move || {
+            seed = seed
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            (seed >> 33) as usize
+        }
+    }
+
+    #[test]
+    fn random_edits_match_batch() -> Result<()> {
+        let mut next = generator(0x2023_0003);
+        let cells = ['.', '.', '.', '1', '2', '7', '9', '0', '*', '#', '+'];
+
+        for counting in [PartCounting::PerSymbol, PartCounting::Unique] {
+            for (gear, neighbourhood) in [
+                ("*:=2:product", "chebyshev:1"),
+                ("*#:>=1:sum", "orthogonal"),
+                ("*+:<=3:max", "manhattan:2"),
+                ("*:=2:product", "chebyshev:2"),
+            ] {
+                let mut schematic = EditableSchematic::new(
+                    include_str!("../../inputs/tests/03.txt"),
+                    counting,
+                    gear.parse()?,
+                    neighbourhood.parse()?,
+                )?;
+
+                for _ in 0..200 {
+                    let position = (next() % 10, next() % 10);
+                    schematic.set(position, cells[next() % cells.len()])?;
+                    assert_matches_batch(&schematic)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn random_signed_edits_match_batch() -> Result<()> {
+        let mut next = generator(0x2023_0048);
+        let cells = ['.', '.', '-', '-', '1', '2', '7', '0', '*', '#'];
+
+        for minus in [Minus::Sign, Minus::SignAndSymbol] {
+            for counting in [PartCounting::PerSymbol, PartCounting::Unique] {
+                let mut schematic = EditableSchematic::signed(
+                    include_str!("../../inputs/tests/03.txt"),
+                    counting,
+                    "*-:>=1:sum".parse()?,
+                    Neighbourhood::default(),
+                    minus,
+                )?;
+
+                for _ in 0..200 {
+                    let position = (next() % 10, next() % 10);
+                    schematic.set(position, cells[next() % cells.len()])?;
+                    assert_matches_batch(&schematic)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,235 @@
+//! Definition of gears: which symbols qualify, how many numbers they need, and
+//! how these numbers make the gear ratio.
+use std::fmt;
+use std::str::FromStr;
+
+use aoc_lib::{Error, Result, answer::CheckedIterator, math::Integer};
+
+/// Required count of adjacent numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arity {
+    /// Exactly this many numbers, at least one.
+    Exact(usize),
+    /// At least this many numbers.
+    Min(usize),
+    /// At least one and at most this many numbers.
+    Max(usize),
+}
+
+impl Arity {
+    pub fn accepts(&self, count: usize) -> bool {
+        match *self {
+            Arity::Exact(n) => count == n && count >= 1,
+            Arity::Min(n) => count >= n.max(1),
+            Arity::Max(n) => (1..=n).contains(&count),
+        }
+    }
+}
+
+/// Print the arity as in a gear specification: `=N`, `>=N` or `<=N`.
+impl fmt::Display for Arity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Arity::Exact(n) => write!(f, "={n}"),
+            Arity::Min(n) => write!(f, ">={n}"),
+            Arity::Max(n) => write!(f, "<={n}"),
+        }
+    }
+}
+
+/// Why a gear candidate is not a gear.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rejection {
+    /// The count of adjacent numbers does not match the gear's arity.
+    Arity { count: usize, expected: Arity },
+    /// The gear ratio does not fit in its integer type.
+    Overflow,
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rejection::Arity { count, expected } => {
+                write!(f, "{count} adjacent numbers, expected {expected}")
+            }
+            Rejection::Overflow => write!(f, "ratio overflow"),
+        }
+    }
+}
+
+/// How the adjacent numbers of a gear make its ratio.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregation {
+    Product,
+    Sum,
+    Max,
+}
+
+impl Aggregation {
+    pub fn apply<T: Integer>(&self, values: impl Iterator<Item = T>) -> Result<T> {
+        match self {
+            Aggregation::Product => values.checked_product("gear ratio"),
+            Aggregation::Sum => values.checked_sum("gear ratio"),
+            Aggregation::Max => Ok(values.max().unwrap_or(T::ZERO)),
+        }
+    }
+}
+
+/// Rule making a symbol a gear.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GearSpec {
+    /// Symbols that can be gears.
+    pub symbols: Vec<char>,
+    pub arity: Arity,
+    pub aggregation: Aggregation,
+}
+
+impl GearSpec {
+    /// Check if a symbol `sym` with `count` adjacent numbers is a gear.
+    pub fn is_gear(&self, sym: char, count: usize) -> bool {
+        self.symbols.contains(&sym) && self.arity.accepts(count)
+    }
+
+    /// Evaluate a symbol `sym` whose adjacent numbers have the given values:
+    /// `None` if the symbol can never be a gear, else its ratio or why it is
+    /// not a gear.
+    pub fn ratio<T: Integer>(
+        &self,
+        sym: char,
+        values: impl ExactSizeIterator<Item = T>,
+    ) -> Option<std::result::Result<T, Rejection>> {
+        if !self.symbols.contains(&sym) {
+            return None;
+        }
+
+        let count = values.len();
+        Some(if self.arity.accepts(count) {
+            self.aggregation
+                .apply(values)
+                .map_err(|_| Rejection::Overflow)
+        } else {
+            Err(Rejection::Arity {
+                count,
+                expected: self.arity,
+            })
+        })
+    }
+}
+
+/// The puzzle's gears: `*` with exactly two numbers, multiplied.
+impl Default for GearSpec {
+    fn default() -> Self {
+        Self {
+            symbols: vec!['*'],
+            arity: Arity::Exact(2),
+            aggregation: Aggregation::Product,
+        }
+    }
+}
+
+/// Parse a specification `<symbols>:<arity>:<aggregation>`, e.g. `*:=2:product`
+/// or `*#:>=2:sum`. The arity is `=N` with `N` at least 1, `>=N` or `<=N`, the
+/// aggregation one of `product`, `sum` and `max`.
+impl FromStr for GearSpec {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || {
+            Error::ParseError(format!(
+                "invalid gear specification '{s}', expected <symbols>:<arity>:<aggregation>"
+            ))
+        };
+
+        // Split from the right, so that ':' can be one of the symbols.
+        let mut parts = s.rsplitn(3, ':');
+        let (Some(aggregation), Some(arity), Some(symbols)) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid());
+        };
+
+        let count = |n: &str| n.parse::<usize>().map_err(|_| invalid());
+        let arity = if let Some(n) = arity.strip_prefix(">=") {
+            Arity::Min(count(n)?)
+        } else if let Some(n) = arity.strip_prefix("<=") {
+            Arity::Max(count(n)?)
+        } else if let Some(n) = arity.strip_prefix('=') {
+            match count(n)? {
+                0 => return Err(invalid()),
+                n => Arity::Exact(n),
+            }
+        } else {
+            return Err(invalid());
+        };
+        let aggregation = match aggregation {
+            "product" => Aggregation::Product,
+            "sum" => Aggregation::Sum,
+            "max" => Aggregation::Max,
+            _ => return Err(invalid()),
+        };
+        if symbols.is_empty() {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            symbols: symbols.chars().collect(),
+            arity,
+            aggregation,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_gear_specs() -> Result<()> {
+        assert_eq!("*:=2:product".parse::<GearSpec>()?, GearSpec::default());
+        assert_eq!(
+            "*::>=3:max".parse::<GearSpec>()?,
+            GearSpec {
+                symbols: vec!['*', ':'],
+                arity: Arity::Min(3),
+                aggregation: Aggregation::Max,
+            }
+        );
+        assert!("*:2:product".parse::<GearSpec>().is_err());
+        assert!(":=2:sum".parse::<GearSpec>().is_err());
+        assert!("*:=2:mean".parse::<GearSpec>().is_err());
+        assert!("*:=0:sum".parse::<GearSpec>().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn arities() {
+        assert!(Arity::Exact(2).accepts(2));
+        assert!(!Arity::Exact(2).accepts(3));
+        assert!(!Arity::Exact(0).accepts(0));
+        assert!(Arity::Min(2).accepts(5));
+        assert!(!Arity::Min(0).accepts(0));
+        assert!(Arity::Max(2).accepts(1));
+        assert!(!Arity::Max(2).accepts(0));
+        assert!(!Arity::Max(2).accepts(3));
+        assert_eq!(Arity::Min(2).to_string(), ">=2");
+    }
+
+    #[test]
+    fn ratios() {
+        let spec = GearSpec::default();
+        assert_eq!(spec.ratio('#', [2u8, 3].into_iter()), None);
+        assert_eq!(spec.ratio('*', [2u8, 3].into_iter()), Some(Ok(6)));
+        assert_eq!(
+            spec.ratio('*', [2u8].into_iter()),
+            Some(Err(Rejection::Arity {
+                count: 1,
+                expected: Arity::Exact(2)
+            }))
+        );
+        assert_eq!(
+            spec.ratio('*', [16u8, 16].into_iter()),
+            Some(Err(Rejection::Overflow))
+        );
+    }
+}
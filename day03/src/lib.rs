@@ -0,0 +1,9 @@
+pub mod edit;
+pub mod gear;
+pub mod machine;
+pub mod neighbourhood;
+pub mod render;
+pub mod repl;
+pub mod report;
+pub mod schematic;
+pub mod stream;
@@ -0,0 +1,289 @@
+//! Machines of a schematic: groups of symbols and numbers connected by adjacency.
+//!
+//! Symbols and numbers are the nodes of a graph whose edges link each symbol to
+//! its adjacent numbers, and symbols or numbers touching each other, as `*#`,
+//! with the same neighbourhood. Two symbols sharing a number are then in the
+//! same machine, as are two numbers sharing a symbol. Numbers connected to no
+//! symbol belong to no machine.
+use std::collections::HashMap;
+
+use aoc_lib::{Result, answer::CheckedIterator, graph::connected_components};
+
+use crate::{
+    gear::GearSpec,
+    neighbourhood::Neighbourhood,
+    schematic::{Number, NumberId, Symbol, Value, adjacent_numbers},
+};
+
+/// Node of the adjacency graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Node {
+    Number(NumberId),
+    /// Symbol, by its index.
+    Symbol(usize),
+}
+
+/// Connected group of symbols and numbers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Machine<V = usize> {
+    /// Symbols, sorted by position.
+    pub symbols: Vec<Symbol>,
+    /// Numbers with their line number, sorted by position.
+    pub numbers: Vec<(usize, Number<V>)>,
+    /// Count of symbols which are gears.
+    pub gears: usize,
+}
+
+impl<V: Value> Machine<V> {
+    /// Count of symbols and numbers.
+    pub fn members(&self) -> usize {
+        self.symbols.len() + self.numbers.len()
+    }
+
+    /// Sum of the values of the numbers.
+    pub fn sum(&self) -> Result<V::Total> {
+        self.numbers
+            .iter()
+            .map(|(_, number)| number.value.total())
+            .checked_sum("sum of machine numbers")
+    }
+
+    /// Top-left and bottom-right cells of the smallest rectangle holding the
+    /// machine, as (line_number, column_number).
+    pub fn bounding_box(&self) -> ((usize, usize), (usize, usize)) {
+        let cells = self
+            .symbols
+            .iter()
+            .map(|symbol| (symbol.position, symbol.position))
+            .chain(self.numbers.iter().map(|(line, number)| {
+                (
+                    (*line, number.position),
+                    (*line, number.position + number.len - 1),
+                )
+            }));
+
+        cells
+            .reduce(|(top_left, bottom_right), (first, last)| {
+                (
+                    (top_left.0.min(first.0), top_left.1.min(first.1)),
+                    (bottom_right.0.max(last.0), bottom_right.1.max(last.1)),
+                )
+            })
+            .expect("a machine has at least one symbol")
+    }
+}
+
+/// Group the symbols and numbers of a schematic into machines, in the order of
+/// their first symbol.
+pub fn machines<V: Value>(
+    numbers: &[Vec<Number<V>>],
+    symbols: &[Symbol],
+    gear: &GearSpec,
+    neighbourhood: Neighbourhood,
+) -> Vec<Machine<V>> {
+    let mut edges: HashMap<Node, Vec<Node>> = HashMap::new();
+    let mut link = |a: Node, b: Node| {
+        edges.entry(a).or_default().push(b);
+        edges.entry(b).or_default().push(a);
+    };
+    let mut gears = vec![false; symbols.len()];
+
+    for (index, symbol) in symbols.iter().enumerate() {
+        let adjacent: Vec<NumberId> = adjacent_numbers(numbers, symbol.position, neighbourhood)
+            .map(|(line, number)| (line, number.position))
+            .collect();
+        gears[index] = gear.is_gear(symbol.sym, adjacent.len());
+
+        for id in adjacent {
+            link(Node::Symbol(index), Node::Number(id));
+        }
+    }
+
+    let symbol_indices: HashMap<(usize, usize), usize> = symbols
+        .iter()
+        .enumerate()
+        .map(|(index, symbol)| (symbol.position, index))
+        .collect();
+    for (index, symbol) in symbols.iter().enumerate() {
+        for other in touching_symbols(&symbol_indices, symbol.position, neighbourhood) {
+            // Each pair is seen from both symbols, link it once.
+            if other > index {
+                link(Node::Symbol(index), Node::Symbol(other));
+            }
+        }
+    }
+
+    for (line, numbers_in_line) in numbers.iter().enumerate() {
+        for number in numbers_in_line {
+            for other in touching_numbers(numbers, line, number, neighbourhood) {
+                if other > (line, number.position) {
+                    link(Node::Number((line, number.position)), Node::Number(other));
+                }
+            }
+        }
+    }
+
+    connected_components((0..symbols.len()).map(Node::Symbol), |node| {
+        edges.get(node).cloned().unwrap_or_default()
+    })
+    .into_iter()
+    .map(|component| {
+        let mut machine = Machine {
+            symbols: Vec::new(),
+            numbers: Vec::new(),
+            gears: 0,
+        };
+        for node in component {
+            match node {
+                Node::Symbol(index) => {
+                    machine.symbols.push(symbols[index]);
+                    machine.gears += usize::from(gears[index]);
+                }
+                Node::Number((line, position)) => {
+                    let index = numbers[line]
+                        .binary_search_by_key(&position, |number| number.position)
+                        .expect("numbers of the graph come from the schematic");
+                    machine.numbers.push((line, numbers[line][index]));
+                }
+            }
+        }
+        machine.symbols.sort_by_key(|symbol| symbol.position);
+        machine
+            .numbers
+            .sort_by_key(|(line, number)| (*line, number.position));
+        machine
+    })
+    .collect()
+}
+
+/// Find the indices of the symbols in the neighbourhood of `position`.
+fn touching_symbols(
+    symbol_indices: &HashMap<(usize, usize), usize>,
+    (line, column): (usize, usize),
+    neighbourhood: Neighbourhood,
+) -> impl Iterator<Item = usize> + '_ {
+    let radius = neighbourhood.radius();
+    (line.saturating_sub(radius)..=line.saturating_add(radius)).flat_map(move |other_line| {
+        let reach = neighbourhood.column_reach(line.abs_diff(other_line));
+        reach
+            .into_iter()
+            .flat_map(move |reach| column.saturating_sub(reach)..=column.saturating_add(reach))
+            .filter(move |other_column| (other_line, *other_column) != (line, column))
+            .filter_map(move |other_column| symbol_indices.get(&(other_line, other_column)))
+            .copied()
+    })
+}
+
+/// Find the other numbers with a digit in the neighbourhood of a digit of
+/// `number`, on line `line`.
+fn touching_numbers<'a, V>(
+    numbers: &'a [Vec<Number<V>>],
+    line: usize,
+    number: &'a Number<V>,
+    neighbourhood: Neighbourhood,
+) -> impl Iterator<Item = NumberId> + 'a {
+    let radius = neighbourhood.radius();
+    let (start, end) = (number.position, number.position + number.len - 1);
+
+    (line.saturating_sub(radius)..=line.saturating_add(radius)).flat_map(move |other_line| {
+        let reach = neighbourhood.column_reach(line.abs_diff(other_line));
+        let others =
+            numbers
+                .get(other_line)
+                .zip(reach)
+                .map_or(&[][..], |(numbers_in_line, reach)| {
+                    // Numbers are sorted by position, and so are their ends.
+                    let first = numbers_in_line
+                        .partition_point(|other| other.position + other.len + reach <= start);
+                    let last = first
+                        + numbers_in_line[first..]
+                            .partition_point(|other| other.position <= end.saturating_add(reach));
+                    &numbers_in_line[first..last]
+                });
+        others
+            .iter()
+            .filter(move |other| (other_line, other.position) != (line, number.position))
+            .map(move |other| (other_line, other.position))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schematic::parse_input;
+
+    #[test]
+    fn sample_machines() -> Result<()> {
+        let (numbers, symbols) = parse_input(include_str!("../../inputs/tests/03.txt"))?;
+        let machines = machines(
+            &numbers,
+            &symbols,
+            &GearSpec::default(),
+            Neighbourhood::default(),
+        );
+
+        // No number is shared and nothing touches, so each symbol makes its own machine.
+        assert_eq!(machines.len(), 6);
+        let stats = |machine: &Machine| -> Result<_> {
+            Ok((
+                machine.members(),
+                machine.sum()?,
+                machine.gears,
+                machine.bounding_box(),
+            ))
+        };
+        assert_eq!(stats(&machines[0])?, (3, 467 + 35, 1, ((0, 0), (2, 3))));
+        assert_eq!(stats(&machines[2])?, (2, 617, 0, ((4, 0), (4, 3))));
+        assert_eq!(stats(&machines[5])?, (3, 755 + 598, 1, ((7, 5), (9, 8))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn connected_members_join_machines() -> Result<()> {
+        let (numbers, symbols) = parse_input("1*2*3...\n......4.\n.....#..\n7.......\n$.......")?;
+        let grouped = machines(
+            &numbers,
+            &symbols,
+            &"*:>=2:sum".parse()?,
+            Neighbourhood::default(),
+        );
+
+        assert_eq!(grouped.len(), 3);
+        assert_eq!(grouped[0].symbols.len(), 2);
+        assert_eq!(grouped[0].sum()?, 1 + 2 + 3);
+        assert_eq!(grouped[0].gears, 2);
+        assert_eq!(grouped[0].bounding_box(), ((0, 0), (0, 4)));
+        assert_eq!(grouped[1].members(), 2);
+        assert_eq!(grouped[1].bounding_box(), ((1, 5), (2, 6)));
+        assert_eq!(grouped[2].sum()?, 7);
+
+        // Touching symbols, and a number touching a part number.
+        let (numbers, symbols) = parse_input("1*#2\n....\n.5..\n..3$")?;
+        let touching = machines(
+            &numbers,
+            &symbols,
+            &GearSpec::default(),
+            Neighbourhood::default(),
+        );
+        assert_eq!(touching.len(), 2);
+        assert_eq!(touching[0].symbols.len(), 2);
+        assert_eq!(touching[0].sum()?, 1 + 2);
+        assert_eq!(touching[1].sum()?, 5 + 3);
+        assert_eq!(touching[1].bounding_box(), ((2, 1), (3, 3)));
+
+        // Two symbols linked by a single number.
+        let (numbers, symbols) = parse_input("*...\n.12.\n...+")?;
+        let linked = machines(
+            &numbers,
+            &symbols,
+            &GearSpec::default(),
+            Neighbourhood::default(),
+        );
+        assert_eq!(linked.len(), 1);
+        assert_eq!(linked[0].members(), 3);
+        assert_eq!(linked[0].bounding_box(), ((0, 0), (2, 3)));
+
+        Ok(())
+    }
+}
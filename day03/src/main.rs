@@ -16,255 +16,266 @@
 //! Then all adjacent numbers are summed up, and the operation
 //! is repeated for all symbols.
 //!
+//! With `--unique-parts`, each number is counted once even if several symbols
+//! are adjacent to it, numbers being identified by their line and position.
+//!
 //! # Part 2 algorithm:
 //!
 //! For each `*` symbol, we check if it has exactly two numbers
 //! adjacent to it. Then these numbers are multiplied.
-use aoc_lib::Result;
-
-/// Symbol struct
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct Symbol {
-    position: (usize, usize),
-    sym: char,
-}
-
-/// Struct that holds the position of a number
-/// on a line, as well as its length and its value as `usize`.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct Number {
-    position: usize,
-    len: usize,
-    value: usize,
-}
-
-impl Number {
-    /// Parse a number from a string, starting at `start_position`.
-    fn try_from_input(start_position: usize, input: &str) -> Result<Self> {
-        Ok(Self {
-            position: start_position,
-            len: input.len(),
-            value: input.parse()?,
-        })
-    }
+//!
+//! Adjacency can be changed with `--adjacency <orthogonal|chebyshev:K|manhattan:K>`,
+//! see [`day03::neighbourhood::Neighbourhood`].
+//!
+//! Other gear rules can be given with `--gear <symbols>:<arity>:<aggregation>`,
+//! see [`day03::gear::GearSpec`].
+//!
+//! With `--stream`, the input is read line by line and only the lines around the
+//! symbols being evaluated are kept, see [`day03::stream::StreamingEvaluator`].
+//!
+//! With `--render <ansi|html|svg>`, the schematic is drawn with its part numbers,
+//! symbols and gears highlighted instead, see [`day03::render::Layout`].
+//!
+//! With `--report <json|csv:numbers|csv:symbols|csv:gears>`, every number, symbol
+//! and gear candidate is exported instead, see [`day03::report::Report`].
+//!
+//! With `--minus <sign|sign-and-symbol>`, a '-' directly before digits is the
+//! sign of the number in every mode, see [`day03::schematic::Minus`].
+use std::fs::File;
+use std::io::BufReader;
 
-    /// Check if the number is adjacent to the given position.
-    fn is_adjacent_to(&self, position: usize) -> bool {
-        position >= self.position.saturating_sub(1) && position <= self.position + self.len
-    }
-}
+use aoc_lib::{Error, Result};
+use day03::{
+    edit::EditableSchematic,
+    gear::GearSpec,
+    neighbourhood::Neighbourhood,
+    render::{Layout, RenderFormat},
+    repl,
+    report::{Report, ReportFormat},
+    schematic::{Minus, PartCounting, Value, shared_numbers},
+    stream::StreamingEvaluator,
+};
 
-/// Enum to help parsing the input.
-enum ParseState {
-    LookingForDigitOrSymbol,
-    LookingForNumberEnd { start: usize },
+/// Command-line options.
+#[derive(Debug, Default)]
+struct Options {
+    /// Start the REPL instead of printing the answers.
+    repl: bool,
+    /// Evaluate the input line by line, in bounded memory.
+    stream: bool,
+    input_path: Option<String>,
+    counting: PartCounting,
+    gear: GearSpec,
+    neighbourhood: Neighbourhood,
+    /// How a '-' directly before digits is read.
+    minus: Minus,
+    /// Draw the schematic instead of printing the answers.
+    render: Option<RenderFormat>,
+    /// Export the report of the schematic instead of printing the answers.
+    report: Option<ReportFormat>,
 }
 
-/// Parse the challenge's input, returning found numbers in a two-dimension array of [`Number`],
-/// and symbols in a `Vec<Symbol>`.
-fn parse_input(input: &str) -> Result<(Vec<Vec<Number>>, Vec<Symbol>)> {
-    input.lines().enumerate().try_fold(
-        (Vec::new(), Vec::new()),
-        |(mut numbers, mut symbols), (line_number, line)| {
-            let mut numbers_in_line = Vec::new();
-            let mut state = ParseState::LookingForDigitOrSymbol;
+impl Options {
+    /// Parse options: `[repl [input]]`, `--unique-parts`, `--gear <spec>`,
+    /// `--adjacency <neighbourhood>`, `--stream`, `--render <format>`,
+    /// `--report <format>` and `--minus <mode>`.
+    fn from_args(args: impl IntoIterator<Item = String>) -> Result<Self> {
+        let mut options = Self::default();
+        let mut args = args.into_iter();
 
-            for (position, character) in line.chars().enumerate() {
-                match (character, &state) {
-                    // If we are looking for a digit and found one, we record its position and
-                    // change state to look for the number's end.
-                    (c, ParseState::LookingForDigitOrSymbol) if c.is_digit(10) => {
-                        state = ParseState::LookingForNumberEnd { start: position }
-                    }
-                    // If we are looking for a symbol and found one, we add its position into
-                    // the array.
-                    (c, ParseState::LookingForDigitOrSymbol) if c != '.' => {
-                        symbols.push(Symbol {
-                            position: (line_number, position),
-                            sym: c,
-                        });
-                    }
-                    // If we are looking for a number's end and found it, we add the number into the array.
-                    // If the character is also a symbol, we add its position into the array.
-                    (c, ParseState::LookingForNumberEnd { start }) if !c.is_digit(10) => {
-                        numbers_in_line
-                            .push(Number::try_from_input(*start, &line[*start..position])?);
-                        if c != '.' {
-                            symbols.push(Symbol {
-                                position: (line_number, position),
-                                sym: c,
-                            });
-                        }
-                        state = ParseState::LookingForDigitOrSymbol;
-                    }
-                    (_, _) => (),
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--unique-parts" => options.counting = PartCounting::Unique,
+                "--stream" => options.stream = true,
+                "--gear" => {
+                    options.gear = args
+                        .next()
+                        .ok_or_else(|| {
+                            Error::ParseError("--gear expects a specification".to_string())
+                        })?
+                        .parse()?;
                 }
+                "--adjacency" => {
+                    options.neighbourhood = args
+                        .next()
+                        .ok_or_else(|| {
+                            Error::ParseError("--adjacency expects a neighbourhood".to_string())
+                        })?
+                        .parse()?;
+                }
+                "--render" => {
+                    options.render = Some(
+                        args.next()
+                            .ok_or_else(|| {
+                                Error::ParseError("--render expects ansi, html or svg".to_string())
+                            })?
+                            .parse()?,
+                    );
+                }
+                "--report" => {
+                    options.report = Some(
+                        args.next()
+                            .ok_or_else(|| {
+                                Error::ParseError(
+                                    "--report expects json, csv:numbers, csv:symbols or csv:gears"
+                                        .to_string(),
+                                )
+                            })?
+                            .parse()?,
+                    );
+                }
+                "--minus" => {
+                    options.minus = args
+                        .next()
+                        .ok_or_else(|| {
+                            Error::ParseError(
+                                "--minus expects symbol, sign or sign-and-symbol".to_string(),
+                            )
+                        })?
+                        .parse()?;
+                }
+                "repl" if !options.repl => options.repl = true,
+                _ if options.repl && options.input_path.is_none() && !arg.starts_with("--") => {
+                    options.input_path = Some(arg)
+                }
+                _ => return Err(Error::ParseError(format!("unknown argument '{arg}'"))),
             }
+        }
 
-            // If we are still looking for a number's end, we end it here.
-            if let ParseState::LookingForNumberEnd { start } = state {
-                numbers_in_line.push(Number::try_from_input(start, &line[start..])?);
-            }
-
-            numbers.push(numbers_in_line);
-            Ok((numbers, symbols))
-        },
-    )
-}
+        if options.repl && options.stream {
+            return Err(Error::ParseError(
+                "--stream cannot be used with the REPL".to_string(),
+            ));
+        }
+        if options.stream && (options.render.is_some() || options.report.is_some()) {
+            return Err(Error::ParseError(
+                "--render and --report need the whole schematic, not --stream".to_string(),
+            ));
+        }
 
-/// Compute the sum of numbers adjacent to the provided position on one line.
-fn sum_numbers_adjacent_to(numbers: &[Number], symbol_position: usize) -> usize {
-    numbers
-        .iter()
-        .filter(|number| number.is_adjacent_to(symbol_position))
-        .map(|number| number.value)
-        .sum()
+        Ok(options)
+    }
 }
 
-/// Compute the sum of numbers adjacent to the provided symbol.
-fn sum_numbers_around_symbol(numbers: &[Vec<Number>], symbol: &Symbol) -> usize {
-    let (symbol_line, symbol_col) = symbol.position;
-    // Compute range going from symbol_line - 1 to symbol_line + 1 (inclusive).
-    let range = symbol_line.saturating_sub(1)..=symbol_line + 1;
+fn main() -> Result<()> {
+    let options = Options::from_args(std::env::args().skip(1))?;
+    let input_path = options.input_path.as_deref().unwrap_or("../inputs/03.txt");
 
-    range
-        // Safely index into `numbers` to get a line.
-        .flat_map(|line_number| numbers.get(line_number))
-        .map(|number_line| sum_numbers_adjacent_to(number_line, symbol_col))
-        .sum()
-}
+    if options.stream {
+        let (counting, gear, neighbourhood) =
+            (options.counting, options.gear, options.neighbourhood);
+        return match options.minus {
+            Minus::Symbol => print_streamed(
+                StreamingEvaluator::new(counting, gear, neighbourhood),
+                input_path,
+            ),
+            minus => print_streamed(
+                StreamingEvaluator::signed(counting, gear, neighbourhood, minus),
+                input_path,
+            ),
+        };
+    }
 
-fn part_1(numbers: &[Vec<Number>], symbols: &[Symbol]) -> usize {
-    symbols
-        .iter()
-        .map(|symbol| sum_numbers_around_symbol(numbers, symbol))
-        .sum()
+    let input = std::fs::read_to_string(input_path)?;
+    let (counting, gear, neighbourhood) = (
+        options.counting,
+        options.gear.clone(),
+        options.neighbourhood,
+    );
+    match options.minus {
+        Minus::Symbol => run(
+            EditableSchematic::new(&input, counting, gear, neighbourhood)?,
+            options,
+        ),
+        minus => run(
+            EditableSchematic::signed(&input, counting, gear, neighbourhood, minus)?,
+            options,
+        ),
+    }
 }
 
-fn compute_gear_ratio(numbers: &[Vec<Number>], symbol: &Symbol) -> Option<usize> {
-    let (symbol_line, symbol_col) = symbol.position;
-    // Compute range going from symbol_line - 1 to symbol_line + 1 (inclusive).
-    let range = symbol_line.saturating_sub(1)..=symbol_line + 1;
+/// Evaluate the input line by line, and print the answers of both parts.
+fn print_streamed<V: Value>(evaluator: StreamingEvaluator<V>, input_path: &str) -> Result<()> {
+    let (part_1, part_2) = evaluator.evaluate(BufReader::new(File::open(input_path)?))?;
+    println!("Day 03 - Part 1: {part_1}");
+    println!("Day 03 - Part 2: {part_2}");
 
-    let candidate_numbers: Vec<&Number> = range
-        // Safely index into `numbers` to get a line.
-        .flat_map(|line_number| numbers.get(line_number))
-        // Then iterate over all the numbers of the line
-        .flat_map(|number_line| number_line.iter())
-        // And keep only those adjacent to the symbol
-        .filter(|number| number.is_adjacent_to(symbol_col))
-        .collect();
+    Ok(())
+}
 
-    match candidate_numbers.len() {
-        2 => Some(
-            candidate_numbers
-                .iter()
-                .map(|number| number.value)
-                .product(),
-        ),
-        _ => None,
+/// Run the mode chosen by the options on a parsed schematic.
+fn run<V: Value + 'static>(schematic: EditableSchematic<V>, options: Options) -> Result<()> {
+    if options.repl {
+        return repl::repl(schematic).run_stdio();
+    }
+    let (numbers, symbols) = (schematic.numbers(), schematic.symbols());
+    let (gear, neighbourhood) = (schematic.gear(), schematic.neighbourhood());
+    if let Some(format) = options.render {
+        let layout = Layout::new(numbers, symbols, gear, neighbourhood)?;
+        print!("{}", layout.render(format));
+        return Ok(());
+    }
+    if let Some(format) = options.report {
+        let report = Report::new(numbers, symbols, gear, neighbourhood);
+        println!("{}", report.export(format).trim_end());
+        return Ok(());
     }
-}
 
-fn part_2(numbers: &[Vec<Number>], symbols: &[Symbol]) -> usize {
-    symbols
-        .iter()
-        // Keep only '*' symbols
-        .filter(|symbol| symbol.sym == '*')
-        // And filter those with exactly 2 adjacent numbers, computing their product
-        .filter_map(|symbol| compute_gear_ratio(numbers, symbol))
-        .sum()
+    print_answers(&schematic)
 }
 
-fn main() -> Result<()> {
-    let input = std::fs::read_to_string("../inputs/03.txt")?;
-    let (numbers, symbols) = parse_input(&input)?;
+/// Print the answers of both parts, and the shared numbers in unique mode.
+fn print_answers<V: Value>(schematic: &EditableSchematic<V>) -> Result<()> {
+    let (part_1, part_2) = schematic.totals();
 
-    println!("Day 03 - Part 1: {}", part_1(&numbers, &symbols));
-    println!("Day 03 - Part 2: {}", part_2(&numbers, &symbols));
+    println!("Day 03 - Part 1: {part_1}");
+    if schematic.counting() == PartCounting::Unique {
+        let shared = shared_numbers(
+            schematic.numbers(),
+            schematic.symbols(),
+            schematic.neighbourhood(),
+        );
+        for (line, number, count) in shared {
+            println!(
+                "  {} at ({line}, {}) counted once for {count} symbols",
+                number.value, number.position
+            );
+        }
+    }
+    println!("Day 03 - Part 2: {part_2}");
 
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
-    type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
-
     use super::*;
 
-    const SAMPLE: &str = include_str!("../../inputs/tests/03.txt");
-
-    fn parsed_sample() -> Result<(Vec<Vec<Number>>, Vec<Symbol>)> {
-        let numbers = vec![
-            vec![
-                Number::try_from_input(0, "467")?,
-                Number::try_from_input(5, "114")?,
-            ],
-            vec![],
-            vec![
-                Number::try_from_input(2, "35")?,
-                Number::try_from_input(6, "633")?,
-            ],
-            vec![],
-            vec![Number::try_from_input(0, "617")?],
-            vec![Number::try_from_input(7, "58")?],
-            vec![Number::try_from_input(2, "592")?],
-            vec![Number::try_from_input(6, "755")?],
-            vec![],
-            vec![
-                Number::try_from_input(1, "664")?,
-                Number::try_from_input(5, "598")?,
-            ],
-        ];
-        let symbols = vec![
-            Symbol {
-                position: (1, 3),
-                sym: '*',
-            },
-            Symbol {
-                position: (3, 6),
-                sym: '#',
-            },
-            Symbol {
-                position: (4, 3),
-                sym: '*',
-            },
-            Symbol {
-                position: (5, 5),
-                sym: '+',
-            },
-            Symbol {
-                position: (8, 3),
-                sym: '$',
-            },
-            Symbol {
-                position: (8, 5),
-                sym: '*',
-            },
-        ];
-
-        Ok((numbers, symbols))
-    }
-
     #[test]
-    fn test_parse_sample() -> Result<()> {
-        assert_eq!(parse_input(SAMPLE)?, parsed_sample()?);
-
-        Ok(())
-    }
+    fn test_options() -> Result<()> {
+        let args = |args: &[&str]| Options::from_args(args.iter().map(|arg| arg.to_string()));
 
-    #[test]
-    fn test_part_1() -> Result<()> {
-        let (numbers, symbols) = parsed_sample()?;
-        assert_eq!(part_1(&numbers, &symbols), 4361);
-
-        Ok(())
-    }
-
-    #[test]
-    fn test_part_2() -> Result<()> {
-        let (numbers, symbols) = parsed_sample()?;
-        assert_eq!(part_2(&numbers, &symbols), 467835);
+        let options = args(&["repl", "input.txt", "--gear", "*:>=2:sum"])?;
+        assert!(options.repl);
+        assert_eq!(options.input_path.as_deref(), Some("input.txt"));
+        assert_eq!(options.gear.aggregation, day03::gear::Aggregation::Sum);
+        assert!(args(&["input.txt"]).is_err());
+        assert!(args(&["--gear"]).is_err());
+        assert!(args(&["--stream"])?.stream);
+        assert!(args(&["repl", "--stream"]).is_err());
+        assert_eq!(args(&["--render", "svg"])?.render, Some(RenderFormat::Svg));
+        assert!(args(&["--render", "png"]).is_err());
+        assert!(args(&["--stream", "--render", "ansi"]).is_err());
+        assert_eq!(
+            args(&["--report", "json"])?.report,
+            Some(ReportFormat::Json)
+        );
+        assert!(args(&["--report"]).is_err());
+        assert_eq!(args(&["--minus", "sign"])?.minus, Minus::Sign);
+        assert_eq!(
+            args(&["repl", "--minus", "sign-and-symbol"])?.minus,
+            Minus::SignAndSymbol
+        );
 
         Ok(())
     }
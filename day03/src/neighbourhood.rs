@@ -0,0 +1,139 @@
+//! Neighbourhoods defining which numbers are adjacent to a symbol.
+use std::fmt;
+use std::str::FromStr;
+
+use aoc_lib::{Error, Result};
+
+/// Set of cells around a symbol where a digit makes its number adjacent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Neighbourhood {
+    /// The four cells sharing a side with the symbol.
+    Orthogonal,
+    /// Cells at most k lines and k columns away, diagonals included.
+    Chebyshev(usize),
+    /// Cells at most k steps away, moving orthogonally.
+    Manhattan(usize),
+}
+
+impl Neighbourhood {
+    /// Largest distance in lines between a symbol and an adjacent number.
+    pub fn radius(&self) -> usize {
+        match *self {
+            Neighbourhood::Orthogonal => 1,
+            Neighbourhood::Chebyshev(k) | Neighbourhood::Manhattan(k) => k,
+        }
+    }
+
+    /// Largest distance in columns between a symbol and an adjacent cell
+    /// `line_distance` lines away, or `None` if no cell of that line is adjacent.
+    pub fn column_reach(&self, line_distance: usize) -> Option<usize> {
+        match *self {
+            Neighbourhood::Orthogonal => 1usize.checked_sub(line_distance),
+            Neighbourhood::Chebyshev(k) => (line_distance <= k).then_some(k),
+            Neighbourhood::Manhattan(k) => k.checked_sub(line_distance),
+        }
+    }
+
+    /// Check if a cell `line_distance` lines and `column_distance` columns away
+    /// from a symbol is in the neighbourhood.
+    pub fn contains(&self, line_distance: usize, column_distance: usize) -> bool {
+        match *self {
+            Neighbourhood::Orthogonal => line_distance + column_distance <= 1,
+            Neighbourhood::Chebyshev(k) => line_distance.max(column_distance) <= k,
+            Neighbourhood::Manhattan(k) => line_distance + column_distance <= k,
+        }
+    }
+}
+
+/// The puzzle's neighbourhood: the eight surrounding cells.
+impl Default for Neighbourhood {
+    fn default() -> Self {
+        Neighbourhood::Chebyshev(1)
+    }
+}
+
+/// Parse a neighbourhood: `orthogonal`, `chebyshev:K` or `manhattan:K`.
+impl FromStr for Neighbourhood {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let radius = |k: &str| {
+            k.parse()
+                .map_err(|_| Error::ParseError(format!("invalid neighbourhood radius '{k}'")))
+        };
+
+        match s.split_once(':') {
+            None if s == "orthogonal" => Ok(Neighbourhood::Orthogonal),
+            Some(("chebyshev", k)) => Ok(Neighbourhood::Chebyshev(radius(k)?)),
+            Some(("manhattan", k)) => Ok(Neighbourhood::Manhattan(radius(k)?)),
+            _ => Err(Error::ParseError(format!(
+                "unknown neighbourhood '{s}', expected orthogonal, chebyshev:K or manhattan:K"
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for Neighbourhood {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Neighbourhood::Orthogonal => write!(f, "orthogonal"),
+            Neighbourhood::Chebyshev(k) => write!(f, "chebyshev:{k}"),
+            Neighbourhood::Manhattan(k) => write!(f, "manhattan:{k}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn neighbourhood_cells() {
+        let cells = |neighbourhood: Neighbourhood| {
+            (0..=3)
+                .flat_map(|line| (0..=3).map(move |column| (line, column)))
+                .filter(|(line, column)| neighbourhood.contains(*line, *column))
+                .count()
+        };
+
+        // Cells of one quadrant, symbol included.
+        assert_eq!(cells(Neighbourhood::Orthogonal), 3);
+        assert_eq!(cells(Neighbourhood::Chebyshev(1)), 4);
+        assert_eq!(cells(Neighbourhood::Chebyshev(2)), 9);
+        assert_eq!(cells(Neighbourhood::Manhattan(2)), 6);
+    }
+
+    #[test]
+    fn column_reach_matches_cells() {
+        for neighbourhood in [
+            Neighbourhood::Orthogonal,
+            Neighbourhood::Chebyshev(2),
+            Neighbourhood::Manhattan(3),
+        ] {
+            for line_distance in 0..5 {
+                let reach = (0..10)
+                    .take_while(|column| neighbourhood.contains(line_distance, *column))
+                    .last();
+                assert_eq!(neighbourhood.column_reach(line_distance), reach);
+            }
+        }
+    }
+
+    #[test]
+    fn parse_neighbourhoods() -> Result<()> {
+        for neighbourhood in [
+            Neighbourhood::Orthogonal,
+            Neighbourhood::Chebyshev(3),
+            Neighbourhood::Manhattan(2),
+        ] {
+            assert_eq!(
+                neighbourhood.to_string().parse::<Neighbourhood>()?,
+                neighbourhood
+            );
+        }
+        assert!("chebyshev".parse::<Neighbourhood>().is_err());
+        assert!("euclid:2".parse::<Neighbourhood>().is_err());
+
+        Ok(())
+    }
+}
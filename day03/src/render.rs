@@ -0,0 +1,413 @@
+//! Drawing of a schematic with its part numbers, other numbers, symbols and
+//! gears highlighted, to a terminal, an HTML page or an SVG image.
+use std::collections::BTreeMap;
+use std::fmt::{self, Write};
+use std::str::FromStr;
+
+use aoc_lib::{Error, Result, answer::Answer};
+
+use crate::{
+    gear::GearSpec,
+    neighbourhood::Neighbourhood,
+    schematic::{Number, Symbol, Value, adjacent_symbol_counts, compute_gear_ratio},
+};
+
+/// Output of the renderer.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RenderFormat {
+    /// Text with ANSI colour codes.
+    #[default]
+    Ansi,
+    /// Standalone HTML page.
+    Html,
+    Svg,
+}
+
+impl FromStr for RenderFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "ansi" => Ok(RenderFormat::Ansi),
+            "html" => Ok(RenderFormat::Html),
+            "svg" => Ok(RenderFormat::Svg),
+            _ => Err(Error::ParseError(format!(
+                "unknown render format '{s}', expected ansi, html or svg"
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for RenderFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RenderFormat::Ansi => write!(f, "ansi"),
+            RenderFormat::Html => write!(f, "html"),
+            RenderFormat::Svg => write!(f, "svg"),
+        }
+    }
+}
+
+/// What a cell of the schematic shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellKind {
+    Empty,
+    /// Digit of a number adjacent to a symbol.
+    PartNumber,
+    /// Digit of a number adjacent to no symbol.
+    OtherNumber,
+    Symbol,
+    Gear,
+}
+
+impl CellKind {
+    /// ANSI SGR parameters colouring the cell, if any.
+    fn ansi(&self) -> Option<&'static str> {
+        match self {
+            CellKind::Empty => None,
+            CellKind::PartNumber => Some("32"),
+            CellKind::OtherNumber => Some("2"),
+            CellKind::Symbol => Some("1;34"),
+            CellKind::Gear => Some("1;33"),
+        }
+    }
+
+    /// CSS class of the cell.
+    fn class(&self) -> &'static str {
+        match self {
+            CellKind::Empty => "empty",
+            CellKind::PartNumber => "part",
+            CellKind::OtherNumber => "other",
+            CellKind::Symbol => "symbol",
+            CellKind::Gear => "gear",
+        }
+    }
+
+    /// Colour of the cell in HTML and SVG.
+    fn colour(&self) -> &'static str {
+        match self {
+            CellKind::Empty => "#999999",
+            CellKind::PartNumber => "#2e7d32",
+            CellKind::OtherNumber => "#b0b0b0",
+            CellKind::Symbol => "#1565c0",
+            CellKind::Gear => "#ef6c00",
+        }
+    }
+}
+
+/// Schematic laid out as cells, with the ratio of each gear.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Layout<T = Answer> {
+    pub cells: Vec<Vec<(char, CellKind)>>,
+    /// Ratio of each gear, by position.
+    pub gear_ratios: BTreeMap<(usize, usize), T>,
+}
+
+impl<T: fmt::Display> Layout<T> {
+    /// Lay out the numbers and symbols of a schematic, classifying them with the
+    /// given gear rules and neighbourhood.
+    pub fn new<V: Value<Total = T>>(
+        numbers: &[Vec<Number<V>>],
+        symbols: &[Symbol],
+        gear: &GearSpec,
+        neighbourhood: Neighbourhood,
+    ) -> Result<Self> {
+        let width = numbers
+            .iter()
+            .flatten()
+            .map(|number| number.position + number.len)
+            .chain(symbols.iter().map(|symbol| symbol.position.1 + 1))
+            .max()
+            .unwrap_or(0);
+        let height = symbols
+            .iter()
+            .map(|symbol| symbol.position.0 + 1)
+            .max()
+            .unwrap_or(0)
+            .max(numbers.len());
+        let mut cells = vec![vec![('.', CellKind::Empty); width]; height];
+
+        let parts = adjacent_symbol_counts(numbers, symbols, neighbourhood);
+        for (line_number, numbers_in_line) in numbers.iter().enumerate() {
+            for number in numbers_in_line {
+                let kind = if parts.contains_key(&(line_number, number.position)) {
+                    CellKind::PartNumber
+                } else {
+                    CellKind::OtherNumber
+                };
+                // Keep the leading zeros of the number, after its sign.
+                let digits = format!("{:0len$}", number.value, len = number.len);
+                for (cell, digit) in cells[line_number][number.position..]
+                    .iter_mut()
+                    .zip(digits.chars())
+                {
+                    *cell = (digit, kind);
+                }
+            }
+        }
+
+        let mut gear_ratios = BTreeMap::new();
+        for symbol in symbols {
+            let (line, column) = symbol.position;
+            let kind = match compute_gear_ratio(numbers, symbol, gear, neighbourhood) {
+                Some(ratio) => {
+                    gear_ratios.insert(symbol.position, ratio?);
+                    CellKind::Gear
+                }
+                None => CellKind::Symbol,
+            };
+            cells[line][column] = (symbol.sym, kind);
+        }
+
+        Ok(Self { cells, gear_ratios })
+    }
+
+    /// Draw the schematic in the given format.
+    pub fn render(&self, format: RenderFormat) -> String {
+        match format {
+            RenderFormat::Ansi => self.to_ansi(),
+            RenderFormat::Html => self.to_html(),
+            RenderFormat::Svg => self.to_svg(),
+        }
+    }
+
+    /// Gear ratios, one per line.
+    fn annotations(&self) -> impl Iterator<Item = String> + '_ {
+        self.gear_ratios.iter().map(|(&(line, column), ratio)| {
+            format!(
+                "gear '{}' at ({line}, {column}): ratio {ratio}",
+                self.cells[line][column].0
+            )
+        })
+    }
+
+    fn to_ansi(&self) -> String {
+        let mut output = String::new();
+        for line in &self.cells {
+            for &(c, kind) in line {
+                match kind.ansi() {
+                    Some(code) => write!(output, "\x1b[{code}m{c}\x1b[0m"),
+                    None => write!(output, "{c}"),
+                }
+                .expect("writing to a String cannot fail");
+            }
+            output.push('\n');
+        }
+        for annotation in self.annotations() {
+            output.push_str(&annotation);
+            output.push('\n');
+        }
+        output
+    }
+
+    fn to_html(&self) -> String {
+        let kinds = [
+            CellKind::PartNumber,
+            CellKind::OtherNumber,
+            CellKind::Symbol,
+            CellKind::Gear,
+        ];
+        let mut output = String::from(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>Engine schematic</title>\n<style>\n",
+        );
+        output.push_str("pre { font-family: monospace; }\n");
+        output.push_str(".empty { color: #999999; }\n");
+        for kind in kinds {
+            writeln!(
+                output,
+                ".{} {{ color: {}; font-weight: bold; }}",
+                kind.class(),
+                kind.colour()
+            )
+            .expect("writing to a String cannot fail");
+        }
+        output.push_str("</style>\n</head>\n<body>\n<pre>\n");
+
+        for (line_number, line) in self.cells.iter().enumerate() {
+            for (column, &(c, kind)) in line.iter().enumerate() {
+                let title = self
+                    .gear_ratios
+                    .get(&(line_number, column))
+                    .map(|ratio| format!(" title=\"ratio {ratio}\""))
+                    .unwrap_or_default();
+                write!(
+                    output,
+                    "<span class=\"{}\"{title}>{}</span>",
+                    kind.class(),
+                    escape(c)
+                )
+                .expect("writing to a String cannot fail");
+            }
+            output.push('\n');
+        }
+        output.push_str("</pre>\n<ul>\n");
+        for annotation in self.annotations() {
+            writeln!(output, "<li>{}</li>", escape_str(&annotation))
+                .expect("writing to a String cannot fail");
+        }
+        output.push_str("</ul>\n</body>\n</html>\n");
+        output
+    }
+
+    fn to_svg(&self) -> String {
+        const CELL_WIDTH: usize = 10;
+        const CELL_HEIGHT: usize = 16;
+
+        let width = self.cells.first().map_or(0, Vec::len) * CELL_WIDTH;
+        let height = (self.cells.len() + self.gear_ratios.len()) * CELL_HEIGHT;
+        let mut output = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+             font-family=\"monospace\" font-size=\"14\">\n"
+        );
+
+        for (line_number, line) in self.cells.iter().enumerate() {
+            let y = (line_number + 1) * CELL_HEIGHT - 4;
+            for (column, &(c, kind)) in line.iter().enumerate() {
+                let x = column * CELL_WIDTH;
+                let title = self
+                    .gear_ratios
+                    .get(&(line_number, column))
+                    .map(|ratio| format!("<title>ratio {ratio}</title>"))
+                    .unwrap_or_default();
+                writeln!(
+                    output,
+                    "<text x=\"{x}\" y=\"{y}\" fill=\"{}\">{title}{}</text>",
+                    kind.colour(),
+                    escape(c)
+                )
+                .expect("writing to a String cannot fail");
+            }
+        }
+        for (i, annotation) in self.annotations().enumerate() {
+            let y = (self.cells.len() + i + 1) * CELL_HEIGHT - 4;
+            writeln!(
+                output,
+                "<text x=\"0\" y=\"{y}\" fill=\"{}\">{}</text>",
+                CellKind::Gear.colour(),
+                escape_str(&annotation)
+            )
+            .expect("writing to a String cannot fail");
+        }
+        output.push_str("</svg>\n");
+        output
+    }
+}
+
+/// Escape a character for HTML and SVG.
+fn escape(c: char) -> String {
+    match c {
+        '&' => "&amp;".to_string(),
+        '<' => "&lt;".to_string(),
+        '>' => "&gt;".to_string(),
+        '"' => "&quot;".to_string(),
+        c => c.to_string(),
+    }
+}
+
+fn escape_str(s: &str) -> String {
+    s.chars().map(escape).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schematic::{Minus, parse_input, parse_signed_input};
+
+    fn sample_layout() -> Result<Layout> {
+        let (numbers, symbols) = parse_input(include_str!("../../inputs/tests/03.txt"))?;
+        Layout::new(
+            &numbers,
+            &symbols,
+            &GearSpec::default(),
+            Neighbourhood::default(),
+        )
+    }
+
+    #[test]
+    fn layout_sample() -> Result<()> {
+        let layout = sample_layout()?;
+
+        assert_eq!(layout.cells.len(), 10);
+        assert_eq!(layout.cells[0][0], ('4', CellKind::PartNumber));
+        assert_eq!(layout.cells[0][5], ('1', CellKind::OtherNumber));
+        assert_eq!(layout.cells[1][3], ('*', CellKind::Gear));
+        assert_eq!(layout.cells[3][6], ('#', CellKind::Symbol));
+        assert_eq!(layout.cells[4][3], ('*', CellKind::Symbol));
+        assert_eq!(layout.cells[1][0], ('.', CellKind::Empty));
+        assert_eq!(
+            layout.gear_ratios,
+            BTreeMap::from([((1, 3), 16345), ((8, 5), 451490)])
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn layout_keeps_leading_zeros() -> Result<()> {
+        let (numbers, symbols) = parse_input("007*")?;
+        let layout = Layout::new(
+            &numbers,
+            &symbols,
+            &GearSpec::default(),
+            Neighbourhood::default(),
+        )?;
+        let text: String = layout.cells[0].iter().map(|(c, _)| c).collect();
+
+        assert_eq!(text, "007*");
+
+        let (numbers, symbols) = parse_signed_input("-07*3", Minus::Sign)?;
+        let layout = Layout::new(
+            &numbers,
+            &symbols,
+            &GearSpec::default(),
+            Neighbourhood::default(),
+        )?;
+        let text: String = layout.cells[0].iter().map(|(c, _)| c).collect();
+
+        assert_eq!(text, "-07*3");
+        assert_eq!(layout.gear_ratios, BTreeMap::from([((0, 3), -21)]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn render_formats() -> Result<()> {
+        let layout = sample_layout()?;
+
+        let ansi = layout.render(RenderFormat::Ansi);
+        assert!(ansi.starts_with("\x1b[32m4\x1b[0m"));
+        assert!(ansi.contains("\x1b[1;33m*\x1b[0m"));
+        assert!(ansi.ends_with("gear '*' at (8, 5): ratio 451490\n"));
+
+        let html = layout.render(RenderFormat::Html);
+        assert!(html.contains("<span class=\"gear\" title=\"ratio 16345\">*</span>"));
+        assert!(html.contains("<li>gear '*' at (1, 3): ratio 16345</li>"));
+
+        let svg = layout.render(RenderFormat::Svg);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("<title>ratio 451490</title>*</text>"));
+
+        for format in [RenderFormat::Ansi, RenderFormat::Html, RenderFormat::Svg] {
+            assert_eq!(format.to_string().parse::<RenderFormat>()?, format);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn render_escapes_symbols() -> Result<()> {
+        let (numbers, symbols) = parse_input("1<&")?;
+        let layout = Layout::new(
+            &numbers,
+            &symbols,
+            &GearSpec::default(),
+            Neighbourhood::default(),
+        )?;
+
+        assert!(layout.render(RenderFormat::Html).contains("&lt;</span>"));
+        assert!(layout.render(RenderFormat::Svg).contains("&amp;</text>"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,323 @@
+//! Interactive exploration of a parsed schematic, started with `cargo run -- repl [input]`
+//! or `aoc repl 3 [input]` from the workspace runner. Cells can be changed with
+//! `set` and `clear`, which print the updated sums of parts 1 and 2.
+use aoc_lib::{
+    Error, Result,
+    repl::{Repl, parse_arg},
+};
+
+use crate::{
+    edit::EditableSchematic,
+    machine::machines,
+    schematic::{Number, PartCounting, Value, adjacent_numbers, part_1, part_2, shared_numbers},
+};
+
+/// Numbers adjacent to the given position, with their line number.
+fn adjacent<V: Value>(
+    schematic: &EditableSchematic<V>,
+    position: (usize, usize),
+) -> Vec<(usize, &Number<V>)> {
+    adjacent_numbers(schematic.numbers(), position, schematic.neighbourhood()).collect()
+}
+
+fn format_totals<V: Value>((part_1, part_2): (V::Total, V::Total)) -> String {
+    format!("part 1 {part_1}, part 2 {part_2}")
+}
+
+fn format_number<V: Value>(line: usize, number: &Number<V>) -> String {
+    format!(
+        "{} at ({line}, {}), length {}",
+        number.value, number.position, number.len
+    )
+}
+
+fn show_summary<V: Value>(schematic: &mut EditableSchematic<V>, _: &[&str]) -> Result<String> {
+    let gears = schematic
+        .symbols()
+        .iter()
+        .filter(|symbol| {
+            let count = adjacent(schematic, symbol.position).len();
+            schematic.gear().is_gear(symbol.sym, count)
+        })
+        .count();
+
+    Ok(format!(
+        "{} lines, {} numbers, {} symbols, {gears} gears",
+        schematic.numbers().len(),
+        schematic.numbers().iter().map(Vec::len).sum::<usize>(),
+        schematic.symbols().len()
+    ))
+}
+
+fn list_numbers<V: Value>(schematic: &mut EditableSchematic<V>, args: &[&str]) -> Result<String> {
+    let line: usize = parse_arg(args, 0, "line")?;
+    let numbers = schematic
+        .numbers()
+        .get(line)
+        .ok_or_else(|| Error::ParseError(format!("no line {line}")))?;
+
+    Ok(numbers
+        .iter()
+        .map(|number| format_number(line, number))
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+fn list_symbols<V: Value>(schematic: &mut EditableSchematic<V>, args: &[&str]) -> Result<String> {
+    let filter: Option<char> = args
+        .first()
+        .map(|_| parse_arg(args, 0, "char"))
+        .transpose()?;
+
+    Ok(schematic
+        .symbols()
+        .iter()
+        .filter(|symbol| filter.is_none_or(|sym| sym == symbol.sym))
+        .map(|symbol| {
+            let adjacent = adjacent(schematic, symbol.position);
+            format!(
+                "'{}' at {:?}, {} adjacent numbers",
+                symbol.sym,
+                symbol.position,
+                adjacent.len()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+fn list_adjacent<V: Value>(schematic: &mut EditableSchematic<V>, args: &[&str]) -> Result<String> {
+    let position = (parse_arg(args, 0, "line")?, parse_arg(args, 1, "column")?);
+
+    Ok(adjacent(schematic, position)
+        .into_iter()
+        .map(|(line, number)| format_number(line, number))
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+fn list_shared<V: Value>(schematic: &mut EditableSchematic<V>, _: &[&str]) -> Result<String> {
+    Ok(shared_numbers(
+        schematic.numbers(),
+        schematic.symbols(),
+        schematic.neighbourhood(),
+    )
+    .into_iter()
+    .map(|(line, number, count)| format!("{}, {count} symbols", format_number(line, number)))
+    .collect::<Vec<_>>()
+    .join("\n"))
+}
+
+fn list_machines<V: Value>(schematic: &mut EditableSchematic<V>, _: &[&str]) -> Result<String> {
+    machines(
+        schematic.numbers(),
+        schematic.symbols(),
+        schematic.gear(),
+        schematic.neighbourhood(),
+    )
+    .iter()
+    .map(|machine| {
+        let ((top, left), (bottom, right)) = machine.bounding_box();
+        Ok(format!(
+            "{} members, sum {}, {} gears, ({top}, {left}) to ({bottom}, {right})",
+            machine.members(),
+            machine.sum()?,
+            machine.gears
+        ))
+    })
+    .collect::<Result<Vec<_>>>()
+    .map(|lines| lines.join("\n"))
+}
+
+fn set_cell<V: Value>(schematic: &mut EditableSchematic<V>, args: &[&str]) -> Result<String> {
+    let position = (parse_arg(args, 0, "line")?, parse_arg(args, 1, "column")?);
+    let c: char = parse_arg(args, 2, "char")?;
+
+    Ok(format_totals::<V>(schematic.set(position, c)?))
+}
+
+fn clear_cell<V: Value>(schematic: &mut EditableSchematic<V>, args: &[&str]) -> Result<String> {
+    let position = (parse_arg(args, 0, "line")?, parse_arg(args, 1, "column")?);
+
+    Ok(format_totals::<V>(schematic.clear(position)?))
+}
+
+/// Build the REPL for the given schematic.
+pub fn repl<V: Value + 'static>(schematic: EditableSchematic<V>) -> Repl<impl Sized> {
+    Repl::new("day03> ", schematic)
+        .command(
+            "summary",
+            "",
+            "Count lines, numbers, symbols and gears",
+            show_summary,
+        )
+        .command(
+            "numbers",
+            "<line>",
+            "List the numbers of a line",
+            list_numbers,
+        )
+        .command(
+            "symbols",
+            "[char]",
+            "List symbols, optionally of one kind",
+            list_symbols,
+        )
+        .command(
+            "adjacent",
+            "<line> <column>",
+            "List numbers adjacent to a position",
+            list_adjacent,
+        )
+        .command(
+            "shared",
+            "",
+            "List numbers adjacent to several symbols",
+            list_shared,
+        )
+        .command(
+            "machines",
+            "",
+            "List groups of connected symbols and numbers",
+            list_machines,
+        )
+        .command(
+            "part1",
+            "[unique]",
+            "Run part 1, counting shared numbers once with 'unique'",
+            |schematic, args| {
+                let counting = match args.first() {
+                    None => PartCounting::PerSymbol,
+                    Some(&"unique") => PartCounting::Unique,
+                    Some(arg) => {
+                        return Err(Error::ParseError(format!(
+                            "invalid part 1 mode '{arg}', expected 'unique'"
+                        )));
+                    }
+                };
+                Ok(part_1(
+                    schematic.numbers(),
+                    schematic.symbols(),
+                    counting,
+                    schematic.neighbourhood(),
+                )?
+                .to_string())
+            },
+        )
+        .command(
+            "adjacency",
+            "[neighbourhood]",
+            "Show or set the adjacency, e.g. manhattan:2",
+            |schematic, args| {
+                if let Some(neighbourhood) = args.first() {
+                    schematic.set_neighbourhood(neighbourhood.parse()?)?;
+                }
+                Ok(schematic.neighbourhood().to_string())
+            },
+        )
+        .command(
+            "gear",
+            "[spec]",
+            "Show or set the gear specification, e.g. *:=2:product",
+            |schematic, args| {
+                if let Some(spec) = args.first() {
+                    schematic.set_gear(spec.parse()?)?;
+                }
+                Ok(format!("{:?}", schematic.gear()))
+            },
+        )
+        .command("part2", "", "Run part 2", |schematic, _| {
+            Ok(part_2(
+                schematic.numbers(),
+                schematic.symbols(),
+                schematic.gear(),
+                schematic.neighbourhood(),
+            )?
+            .to_string())
+        })
+        .command(
+            "set",
+            "<line> <column> <char>",
+            "Change a cell of an existing line, and show the new sums",
+            set_cell,
+        )
+        .command(
+            "clear",
+            "<line> <column>",
+            "Empty a cell, and show the new sums",
+            clear_cell,
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gear::GearSpec, neighbourhood::Neighbourhood};
+
+    fn sample_repl() -> Result<Repl<impl Sized>> {
+        Ok(repl(EditableSchematic::new(
+            include_str!("../../inputs/tests/03.txt"),
+            PartCounting::PerSymbol,
+            GearSpec::default(),
+            Neighbourhood::default(),
+        )?))
+    }
+
+    fn run(repl: &mut Repl<impl Sized>, line: &str) -> Result<String> {
+        repl.execute(line).expect("command should not quit")
+    }
+
+    #[test]
+    fn repl_queries() -> Result<()> {
+        let mut repl = sample_repl()?;
+
+        assert_eq!(
+            run(&mut repl, "summary")?,
+            "10 lines, 10 numbers, 6 symbols, 2 gears"
+        );
+        assert_eq!(
+            run(&mut repl, "adjacent 1 3")?,
+            "467 at (0, 0), length 3\n35 at (2, 2), length 2"
+        );
+        assert_eq!(
+            run(&mut repl, "symbols #")?,
+            "'#' at (3, 6), 1 adjacent numbers"
+        );
+        assert_eq!(run(&mut repl, "part1")?, "4361");
+        assert_eq!(run(&mut repl, "part1 unique")?, "4361");
+        assert_eq!(run(&mut repl, "shared")?, "");
+        assert_eq!(
+            run(&mut repl, "machines")?.lines().next(),
+            Some("3 members, sum 502, 1 gears, (0, 0) to (2, 3)")
+        );
+        assert!(run(&mut repl, "numbers 42").is_err());
+        assert_eq!(run(&mut repl, "adjacency orthogonal")?, "orthogonal");
+        assert_eq!(run(&mut repl, "adjacent 1 3")?, "35 at (2, 2), length 2");
+        run(&mut repl, "adjacency chebyshev:1")?;
+        run(&mut repl, "gear *:>=1:sum")?;
+        assert_eq!(run(&mut repl, "part2")?, "2472");
+        assert_eq!(
+            run(&mut repl, "summary")?,
+            "10 lines, 10 numbers, 6 symbols, 3 gears"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn repl_edits() -> Result<()> {
+        let mut repl = sample_repl()?;
+
+        assert_eq!(run(&mut repl, "set 1 4 #")?, "part 1 4510, part 2 467835");
+        assert_eq!(
+            run(&mut repl, "adjacent 1 4")?,
+            "114 at (0, 5), length 3\n35 at (2, 2), length 2"
+        );
+        assert_eq!(run(&mut repl, "clear 1 4")?, "part 1 4361, part 2 467835");
+        assert_eq!(run(&mut repl, "part1")?, "4361");
+        assert!(run(&mut repl, "set 0 10 1").is_err());
+        assert!(run(&mut repl, "set 0 1").is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,409 @@
+//! Detailed report of a schematic: every number, symbol and gear candidate,
+//! exported as JSON or CSV.
+use std::fmt::Write;
+use std::str::FromStr;
+
+use aoc_lib::{Error, Result, answer::Answer, csv};
+
+use crate::{
+    gear::{GearSpec, Rejection},
+    neighbourhood::Neighbourhood,
+    schematic::{Number, NumberId, Symbol, Value, adjacent_numbers, adjacent_symbol_counts},
+};
+
+/// Number of the schematic, with the count of its adjacent symbols.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NumberReport<V = usize> {
+    pub line: usize,
+    pub number: Number<V>,
+    pub adjacent_symbols: usize,
+}
+
+impl<V> NumberReport<V> {
+    /// Check if the number is a part number, i.e. adjacent to a symbol.
+    pub fn is_part(&self) -> bool {
+        self.adjacent_symbols > 0
+    }
+}
+
+/// Symbol of the schematic, with its adjacent numbers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymbolReport<V = usize> {
+    pub symbol: Symbol,
+    /// Adjacent numbers, with their value.
+    pub adjacent: Vec<(NumberId, V)>,
+}
+
+/// Symbol that can be a gear, with its ratio if it is one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GearReport<T = Answer> {
+    pub symbol: Symbol,
+    pub ratio: std::result::Result<T, Rejection>,
+}
+
+/// Table exported as CSV.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CsvTable {
+    Numbers,
+    Symbols,
+    Gears,
+}
+
+/// Export format of a report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Json,
+    Csv(CsvTable),
+}
+
+/// Parse a format: `json`, `csv:numbers`, `csv:symbols` or `csv:gears`.
+impl FromStr for ReportFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "json" => Ok(ReportFormat::Json),
+            "csv:numbers" => Ok(ReportFormat::Csv(CsvTable::Numbers)),
+            "csv:symbols" => Ok(ReportFormat::Csv(CsvTable::Symbols)),
+            "csv:gears" => Ok(ReportFormat::Csv(CsvTable::Gears)),
+            _ => Err(Error::ParseError(format!(
+                "unknown report format '{s}', expected json, csv:numbers, csv:symbols or csv:gears"
+            ))),
+        }
+    }
+}
+
+/// Report of all numbers, symbols and gear candidates of a schematic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report<V: Value = usize> {
+    pub numbers: Vec<NumberReport<V>>,
+    pub symbols: Vec<SymbolReport<V>>,
+    pub gears: Vec<GearReport<V::Total>>,
+}
+
+impl<V: Value> Report<V> {
+    pub fn new(
+        numbers: &[Vec<Number<V>>],
+        symbols: &[Symbol],
+        spec: &GearSpec,
+        neighbourhood: Neighbourhood,
+    ) -> Self {
+        let counts = adjacent_symbol_counts(numbers, symbols, neighbourhood);
+        let number_reports = numbers
+            .iter()
+            .enumerate()
+            .flat_map(|(line, numbers_in_line)| {
+                numbers_in_line.iter().map(move |number| (line, number))
+            })
+            .map(|(line, number)| NumberReport {
+                line,
+                number: *number,
+                adjacent_symbols: counts.get(&(line, number.position)).copied().unwrap_or(0),
+            })
+            .collect();
+
+        let symbol_reports: Vec<SymbolReport<V>> = symbols
+            .iter()
+            .map(|symbol| SymbolReport {
+                symbol: *symbol,
+                adjacent: adjacent_numbers(numbers, symbol.position, neighbourhood)
+                    .map(|(line, number)| ((line, number.position), number.value))
+                    .collect(),
+            })
+            .collect();
+
+        let gears = symbol_reports
+            .iter()
+            .filter_map(|report| {
+                let values = report.adjacent.iter().map(|(_, value)| value.total());
+                spec.ratio(report.symbol.sym, values)
+                    .map(|ratio| GearReport {
+                        symbol: report.symbol,
+                        ratio,
+                    })
+            })
+            .collect();
+
+        Self {
+            numbers: number_reports,
+            symbols: symbol_reports,
+            gears,
+        }
+    }
+
+    /// Export in the given format.
+    pub fn export(&self, format: ReportFormat) -> String {
+        match format {
+            ReportFormat::Json => self.to_json(),
+            ReportFormat::Csv(table) => self.to_csv(table),
+        }
+    }
+
+    /// Export as a JSON object with `numbers`, `symbols` and `gears` arrays.
+    pub fn to_json(&self) -> String {
+        let numbers: Vec<String> = self
+            .numbers
+            .iter()
+            .map(|report| {
+                format!(
+                    "{{\"line\":{},\"column\":{},\"len\":{},\"value\":{},\"part\":{},\"adjacent_symbols\":{}}}",
+                    report.line,
+                    report.number.position,
+                    report.number.len,
+                    report.number.value,
+                    report.is_part(),
+                    report.adjacent_symbols
+                )
+            })
+            .collect();
+        let symbols: Vec<String> = self
+            .symbols
+            .iter()
+            .map(|report| {
+                let adjacent: Vec<String> = report
+                    .adjacent
+                    .iter()
+                    .map(|((line, column), value)| {
+                        format!("{{\"line\":{line},\"column\":{column},\"value\":{value}}}")
+                    })
+                    .collect();
+                format!(
+                    "{{\"line\":{},\"column\":{},\"symbol\":{},\"adjacent\":[{}]}}",
+                    report.symbol.position.0,
+                    report.symbol.position.1,
+                    json_string(&report.symbol.sym.to_string()),
+                    adjacent.join(",")
+                )
+            })
+            .collect();
+        let gears: Vec<String> = self
+            .gears
+            .iter()
+            .map(|report| {
+                let outcome = match &report.ratio {
+                    Ok(ratio) => format!("\"gear\":true,\"ratio\":{ratio}"),
+                    Err(rejection) => format!(
+                        "\"gear\":false,\"rejection\":{}",
+                        json_string(&rejection.to_string())
+                    ),
+                };
+                format!(
+                    "{{\"line\":{},\"column\":{},\"symbol\":{},{outcome}}}",
+                    report.symbol.position.0,
+                    report.symbol.position.1,
+                    json_string(&report.symbol.sym.to_string())
+                )
+            })
+            .collect();
+
+        format!(
+            "{{\"numbers\":[{}],\"symbols\":[{}],\"gears\":[{}]}}",
+            numbers.join(","),
+            symbols.join(","),
+            gears.join(",")
+        )
+    }
+
+    /// Export one table as CSV, header included. Adjacent numbers of a symbol
+    /// are listed as `line:column=value`, separated by `;`.
+    pub fn to_csv(&self, table: CsvTable) -> String {
+        let mut output = String::new();
+        let mut record = |record: String| {
+            output.push_str(&record);
+            output.push('\n');
+        };
+
+        match table {
+            CsvTable::Numbers => {
+                record("line,column,len,value,part,adjacent_symbols".to_string());
+                for report in &self.numbers {
+                    record(format!(
+                        "{},{},{},{},{},{}",
+                        report.line,
+                        report.number.position,
+                        report.number.len,
+                        report.number.value,
+                        report.is_part(),
+                        report.adjacent_symbols
+                    ));
+                }
+            }
+            CsvTable::Symbols => {
+                record("line,column,symbol,adjacent_count,adjacent".to_string());
+                for report in &self.symbols {
+                    let mut adjacent = String::new();
+                    for (i, ((line, column), value)) in report.adjacent.iter().enumerate() {
+                        let separator = if i == 0 { "" } else { ";" };
+                        write!(adjacent, "{separator}{line}:{column}={value}")
+                            .expect("writing to a String cannot fail");
+                    }
+                    record(format!(
+                        "{},{},{},{},{adjacent}",
+                        report.symbol.position.0,
+                        report.symbol.position.1,
+                        csv::field(&report.symbol.sym.to_string()),
+                        report.adjacent.len()
+                    ));
+                }
+            }
+            CsvTable::Gears => {
+                record("line,column,symbol,gear,ratio,rejection".to_string());
+                for report in &self.gears {
+                    let outcome = match &report.ratio {
+                        Ok(ratio) => format!("true,{ratio},"),
+                        Err(rejection) => format!("false,,{}", csv::field(&rejection.to_string())),
+                    };
+                    record(format!(
+                        "{},{},{},{outcome}",
+                        report.symbol.position.0,
+                        report.symbol.position.1,
+                        csv::field(&report.symbol.sym.to_string())
+                    ));
+                }
+            }
+        }
+
+        output
+    }
+}
+
+/// Quote and escape a JSON string.
+fn json_string(s: &str) -> String {
+    let mut quoted = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            c if c.is_control() => {
+                write!(quoted, "\\u{:04x}", c as u32).expect("writing to a String cannot fail")
+            }
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gear::Arity, schematic::parse_input};
+
+    fn sample_report() -> Result<Report> {
+        let (numbers, symbols) = parse_input(include_str!("../../inputs/tests/03.txt"))?;
+        Ok(Report::new(
+            &numbers,
+            &symbols,
+            &GearSpec::default(),
+            Neighbourhood::default(),
+        ))
+    }
+
+    #[test]
+    fn report_sample() -> Result<()> {
+        let report = sample_report()?;
+
+        assert_eq!(report.numbers.len(), 10);
+        let not_parts: Vec<usize> = report
+            .numbers
+            .iter()
+            .filter(|number| !number.is_part())
+            .map(|number| number.number.value)
+            .collect();
+        assert_eq!(not_parts, vec![114, 58]);
+
+        assert_eq!(
+            report.symbols[0],
+            SymbolReport {
+                symbol: Symbol {
+                    position: (1, 3),
+                    sym: '*'
+                },
+                adjacent: vec![((0, 0), 467), ((2, 2), 35)],
+            }
+        );
+
+        let gears: Vec<_> = report
+            .gears
+            .iter()
+            .map(|gear| (gear.symbol.position, gear.ratio))
+            .collect();
+        assert_eq!(
+            gears,
+            vec![
+                ((1, 3), Ok(16345)),
+                (
+                    (4, 3),
+                    Err(Rejection::Arity {
+                        count: 1,
+                        expected: Arity::Exact(2)
+                    })
+                ),
+                ((8, 5), Ok(451490)),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn report_overflow() -> Result<()> {
+        let big = usize::MAX.to_string();
+        let (numbers, symbols) = parse_input(&format!("{big}\n{big}*{big}"))?;
+        let spec: GearSpec = "*:=3:product".parse()?;
+        let report = Report::new(&numbers, &symbols, &spec, Neighbourhood::default());
+
+        assert_eq!(report.gears[0].ratio, Err(Rejection::Overflow));
+
+        Ok(())
+    }
+
+    #[test]
+    fn export_json() -> Result<()> {
+        let json = sample_report()?.to_json();
+
+        assert!(json.starts_with(
+            "{\"numbers\":[{\"line\":0,\"column\":0,\"len\":3,\"value\":467,\"part\":true,\"adjacent_symbols\":1},"
+        ));
+        assert!(json.contains(
+            "{\"line\":1,\"column\":3,\"symbol\":\"*\",\"adjacent\":[{\"line\":0,\"column\":0,\"value\":467},{\"line\":2,\"column\":2,\"value\":35}]}"
+        ));
+        assert!(json.contains(
+            "{\"line\":4,\"column\":3,\"symbol\":\"*\",\"gear\":false,\"rejection\":\"1 adjacent numbers, expected =2\"}"
+        ));
+        assert!(json.ends_with(
+            "{\"line\":8,\"column\":5,\"symbol\":\"*\",\"gear\":true,\"ratio\":451490}]}"
+        ));
+        assert_eq!(json_string("a\"\\\n"), "\"a\\\"\\\\\\u000a\"");
+
+        Ok(())
+    }
+
+    #[test]
+    fn export_csv() -> Result<()> {
+        let report = sample_report()?;
+
+        let numbers = report.export("csv:numbers".parse()?);
+        assert_eq!(
+            numbers.lines().take(3).collect::<Vec<_>>(),
+            vec![
+                "line,column,len,value,part,adjacent_symbols",
+                "0,0,3,467,true,1",
+                "0,5,3,114,false,0"
+            ]
+        );
+        let symbols = report.export("csv:symbols".parse()?);
+        assert_eq!(symbols.lines().nth(1), Some("1,3,*,2,0:0=467;2:2=35"));
+        let gears = report.export("csv:gears".parse()?);
+        assert_eq!(
+            gears,
+            "line,column,symbol,gear,ratio,rejection\n\
+             1,3,*,true,16345,\n\
+             4,3,*,false,,\"1 adjacent numbers, expected =2\"\n\
+             8,5,*,true,451490,\n"
+        );
+        assert!("csv".parse::<ReportFormat>().is_err());
+
+        Ok(())
+    }
+}
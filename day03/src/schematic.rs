@@ -0,0 +1,818 @@
+//! Numbers and symbols of an engine schematic, and the sums of parts 1 and 2.
+use std::collections::BTreeMap;
+use std::fmt;
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+use aoc_lib::{
+    Error, Result,
+    answer::{Answer, CheckedIterator, TryCheckedIterator},
+    interval::Interval,
+    math::Integer,
+};
+
+use crate::{
+    gear::{GearSpec, Rejection},
+    neighbourhood::Neighbourhood,
+};
+
+/// Symbol struct
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Symbol {
+    pub position: (usize, usize),
+    pub sym: char,
+}
+
+/// Value of a number: `usize` by default, `isize` when '-' can be a sign.
+pub trait Value: Copy + Eq + fmt::Debug + fmt::Display + FromStr<Err = ParseIntError> {
+    /// Integer type of the sums and gear ratios of values.
+    type Total: Integer + fmt::Display;
+
+    fn total(self) -> Self::Total;
+}
+
+impl Value for usize {
+    type Total = Answer;
+
+    fn total(self) -> Answer {
+        self as Answer
+    }
+}
+
+impl Value for isize {
+    type Total = i128;
+
+    fn total(self) -> i128 {
+        self as i128
+    }
+}
+
+/// Struct that holds the position of a number
+/// on a line, as well as its length and its value, `usize` by default.
+///
+/// The position and length of a signed number include its sign.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Number<V = usize> {
+    pub position: usize,
+    pub len: usize,
+    pub value: V,
+}
+
+impl<V: Value> Number<V> {
+    /// Parse a number from a string of ASCII digits, optionally signed,
+    /// starting at `start_position`.
+    pub fn try_from_input(start_position: usize, input: &str) -> Result<Self> {
+        Ok(Self {
+            position: start_position,
+            len: input.len(),
+            value: input.parse()?,
+        })
+    }
+
+    /// Check if the number, `line_distance` lines away from a symbol in the
+    /// given column, is in the symbol's neighbourhood.
+    ///
+    /// A symbol within the number, only possible for its sign with
+    /// [`Minus::SignAndSymbol`], is not adjacent to it.
+    pub fn is_adjacent_to(
+        &self,
+        line_distance: usize,
+        column: usize,
+        neighbourhood: Neighbourhood,
+    ) -> bool {
+        if line_distance == 0 && self.covers(column) {
+            return false;
+        }
+        Interval::from_len(self.position, self.len).is_some_and(|span| {
+            let column_distance = if column < span.start() {
+                span.start() - column
+            } else {
+                column.saturating_sub(span.end())
+            };
+            neighbourhood.contains(line_distance, column_distance)
+        })
+    }
+}
+
+impl<V> Number<V> {
+    /// Check if the number spans the given column of its line.
+    fn covers(&self, column: usize) -> bool {
+        (self.position..self.position.saturating_add(self.len)).contains(&column)
+    }
+}
+
+/// Identity of a number: (line_number, position).
+pub type NumberId = (usize, usize);
+
+/// How a '-' directly before digits is read.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Minus {
+    /// A symbol, as any other character: numbers are unsigned.
+    #[default]
+    Symbol,
+    /// The sign of the number.
+    Sign,
+    /// The sign of the number, also counted as a symbol for the numbers around
+    /// it but not for the number it signs.
+    SignAndSymbol,
+}
+
+/// Parse a minus mode: `symbol`, `sign` or `sign-and-symbol`.
+impl FromStr for Minus {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "symbol" => Ok(Minus::Symbol),
+            "sign" => Ok(Minus::Sign),
+            "sign-and-symbol" => Ok(Minus::SignAndSymbol),
+            _ => Err(Error::ParseError(format!(
+                "unknown minus mode '{s}', expected symbol, sign or sign-and-symbol"
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for Minus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Minus::Symbol => write!(f, "symbol"),
+            Minus::Sign => write!(f, "sign"),
+            Minus::SignAndSymbol => write!(f, "sign-and-symbol"),
+        }
+    }
+}
+
+/// How part 1 counts numbers adjacent to several symbols.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PartCounting {
+    /// Once per adjacent symbol.
+    #[default]
+    PerSymbol,
+    /// Once, whatever the number of adjacent symbols.
+    Unique,
+}
+
+/// Enum to help parsing the input.
+enum ParseState {
+    LookingForDigitOrSymbol,
+    /// Looking for the end of a number starting in column `start`, at byte `start_byte`.
+    LookingForNumberEnd {
+        start: usize,
+        start_byte: usize,
+    },
+}
+
+/// Numbers of a schematic, line by line, and its symbols.
+pub type Parsed<V = usize> = (Vec<Vec<Number<V>>>, Vec<Symbol>);
+
+/// Parse the challenge's input, returning found numbers in a two-dimension array of [`Number`],
+/// and symbols in a `Vec<Symbol>`.
+///
+/// Positions are columns counted in characters, so that lines with multi-byte
+/// characters line up, while numbers are sliced from the line by byte offset.
+pub fn parse_input(input: &str) -> Result<Parsed> {
+    parse(input, Minus::Symbol)
+}
+
+/// Parse the challenge's input as [`parse_input`], reading a '-' directly before
+/// digits as set by `minus`.
+pub fn parse_signed_input(input: &str, minus: Minus) -> Result<Parsed<isize>> {
+    parse(input, minus)
+}
+
+/// Parse the challenge's input with values of type `V`, which must be signed
+/// unless `minus` is [`Minus::Symbol`].
+pub(crate) fn parse<V: Value>(input: &str, minus: Minus) -> Result<Parsed<V>> {
+    input.lines().enumerate().try_fold(
+        (Vec::new(), Vec::new()),
+        |(mut numbers, mut symbols), (line_number, line)| {
+            let mut numbers_in_line = Vec::new();
+            let mut state = ParseState::LookingForDigitOrSymbol;
+            let mut characters = line.char_indices().enumerate().peekable();
+
+            while let Some((column, (byte, character))) = characters.next() {
+                // If we are looking for a number's end and found it, we add the number into the array,
+                // then look at the character as any other.
+                if let ParseState::LookingForNumberEnd { start, start_byte } = state {
+                    if character.is_ascii_digit() {
+                        continue;
+                    }
+                    numbers_in_line.push(Number::try_from_input(start, &line[start_byte..byte])?);
+                    state = ParseState::LookingForDigitOrSymbol;
+                }
+
+                let is_sign = minus != Minus::Symbol
+                    && character == '-'
+                    && characters
+                        .peek()
+                        .is_some_and(|(_, (_, next))| next.is_ascii_digit());
+                let symbol = Symbol {
+                    position: (line_number, column),
+                    sym: character,
+                };
+
+                match character {
+                    // If we found a digit or a sign, we record its position and change state
+                    // to look for the number's end.
+                    c if c.is_ascii_digit() || is_sign => {
+                        state = ParseState::LookingForNumberEnd {
+                            start: column,
+                            start_byte: byte,
+                        };
+                        if is_sign && minus == Minus::SignAndSymbol {
+                            symbols.push(symbol);
+                        }
+                    }
+                    // If we found a symbol, we add its position into the array.
+                    '.' => (),
+                    _ => symbols.push(symbol),
+                }
+            }
+
+            // If we are still looking for a number's end, we end it here.
+            if let ParseState::LookingForNumberEnd { start, start_byte } = state {
+                numbers_in_line.push(Number::try_from_input(start, &line[start_byte..])?);
+            }
+
+            numbers.push(numbers_in_line);
+            Ok((numbers, symbols))
+        },
+    )
+}
+
+/// Find the numbers of a line within `reach` columns of `column`.
+///
+/// Numbers of a line are sorted by position and do not overlap, so their ends
+/// are sorted too: the first one is found by binary search, and the following
+/// ones are taken until they start too far.
+fn numbers_within<V>(numbers: &[Number<V>], column: usize, reach: usize) -> &[Number<V>] {
+    let first = numbers.partition_point(|number| {
+        number
+            .position
+            .saturating_add(number.len)
+            .saturating_add(reach)
+            <= column
+    });
+    let last = first
+        + numbers[first..]
+            .partition_point(|number| number.position <= column.saturating_add(reach));
+
+    &numbers[first..last]
+}
+
+/// Find the numbers adjacent to `position` in `neighbourhood`, with their line number.
+/// A number spanning `position` itself, i.e. signed by a symbol there, is not
+/// adjacent to it.
+///
+/// The numbers of each line must be sorted by position, as returned by
+/// [`parse_input`], which makes the lookup logarithmic in the line's length.
+/// Lines can be any slices of numbers, such as the lines of a streaming window.
+pub fn adjacent_numbers<'a, V: 'a, L: AsRef<[Number<V>]>>(
+    numbers: &'a [L],
+    (line, column): (usize, usize),
+    neighbourhood: Neighbourhood,
+) -> impl Iterator<Item = (usize, &'a Number<V>)> {
+    let radius = neighbourhood.radius();
+    // Compute range going from line - radius to line + radius (inclusive).
+    (line.saturating_sub(radius)..=line.saturating_add(radius))
+        // Safely index into `numbers` to get a line, with the reach of the
+        // neighbourhood on this line.
+        .flat_map(move |line_number| {
+            let reach = neighbourhood.column_reach(line.abs_diff(line_number));
+            numbers.get(line_number).zip(reach).into_iter().flat_map(
+                move |(numbers_in_line, reach)| {
+                    numbers_within(numbers_in_line.as_ref(), column, reach)
+                        .iter()
+                        .filter(move |number| line_number != line || !number.covers(column))
+                        .map(move |number| (line_number, number))
+                },
+            )
+        })
+}
+
+/// Compute the sum of numbers adjacent to the provided symbol.
+pub fn sum_numbers_around_symbol<V: Value, L: AsRef<[Number<V>]>>(
+    numbers: &[L],
+    symbol: &Symbol,
+    neighbourhood: Neighbourhood,
+) -> Result<V::Total> {
+    adjacent_numbers(numbers, symbol.position, neighbourhood)
+        .map(|(_, number)| number.value.total())
+        .checked_sum("sum of part numbers")
+}
+
+/// Count the symbols adjacent to each part number.
+pub fn adjacent_symbol_counts<V>(
+    numbers: &[Vec<Number<V>>],
+    symbols: &[Symbol],
+    neighbourhood: Neighbourhood,
+) -> BTreeMap<NumberId, usize> {
+    let mut counts = BTreeMap::new();
+    for symbol in symbols {
+        for (line_number, number) in adjacent_numbers(numbers, symbol.position, neighbourhood) {
+            *counts.entry((line_number, number.position)).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+/// Find the numbers adjacent to several symbols, with their line number and
+/// their count of adjacent symbols.
+pub fn shared_numbers<'a, V>(
+    numbers: &'a [Vec<Number<V>>],
+    symbols: &[Symbol],
+    neighbourhood: Neighbourhood,
+) -> Vec<(usize, &'a Number<V>, usize)> {
+    adjacent_symbol_counts(numbers, symbols, neighbourhood)
+        .into_iter()
+        .filter(|(_, count)| *count > 1)
+        .flat_map(|((line_number, position), count)| {
+            numbers[line_number]
+                .iter()
+                .find(|number| number.position == position)
+                .map(|number| (line_number, number, count))
+        })
+        .collect()
+}
+
+pub fn part_1<V: Value>(
+    numbers: &[Vec<Number<V>>],
+    symbols: &[Symbol],
+    counting: PartCounting,
+    neighbourhood: Neighbourhood,
+) -> Result<V::Total> {
+    match counting {
+        PartCounting::PerSymbol => symbols
+            .iter()
+            .map(|symbol| sum_numbers_around_symbol(numbers, symbol, neighbourhood))
+            .try_checked_sum("sum of part numbers"),
+        PartCounting::Unique => adjacent_symbol_counts(numbers, symbols, neighbourhood)
+            .into_keys()
+            .flat_map(|(line_number, position)| {
+                numbers[line_number]
+                    .iter()
+                    .find(|number| number.position == position)
+            })
+            .map(|number| number.value.total())
+            .checked_sum("sum of part numbers"),
+    }
+}
+
+pub fn compute_gear_ratio<V: Value, L: AsRef<[Number<V>]>>(
+    numbers: &[L],
+    symbol: &Symbol,
+    spec: &GearSpec,
+    neighbourhood: Neighbourhood,
+) -> Option<Result<V::Total>> {
+    let values: Vec<V::Total> = adjacent_numbers(numbers, symbol.position, neighbourhood)
+        .map(|(_, number)| number.value.total())
+        .collect();
+
+    match spec.ratio(symbol.sym, values.into_iter())? {
+        Ok(ratio) => Some(Ok(ratio)),
+        Err(Rejection::Arity { .. }) => None,
+        Err(Rejection::Overflow) => Some(Err(Error::OverflowError(format!(
+            "gear ratio of '{}' at {:?}",
+            symbol.sym, symbol.position
+        )))),
+    }
+}
+
+pub fn part_2<V: Value>(
+    numbers: &[Vec<Number<V>>],
+    symbols: &[Symbol],
+    spec: &GearSpec,
+    neighbourhood: Neighbourhood,
+) -> Result<V::Total> {
+    symbols
+        .iter()
+        // Keep only gears, computing their ratio
+        .filter_map(|symbol| compute_gear_ratio(numbers, symbol, spec, neighbourhood))
+        .try_checked_sum("sum of gear ratios")
+}
+
+#[cfg(test)]
+mod tests {
+    type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+    use super::*;
+
+    const SAMPLE: &str = include_str!("../../inputs/tests/03.txt");
+
+    fn parsed_sample() -> Result<(Vec<Vec<Number>>, Vec<Symbol>)> {
+        let numbers = vec![
+            vec![
+                Number::try_from_input(0, "467")?,
+                Number::try_from_input(5, "114")?,
+            ],
+            vec![],
+            vec![
+                Number::try_from_input(2, "35")?,
+                Number::try_from_input(6, "633")?,
+            ],
+            vec![],
+            vec![Number::try_from_input(0, "617")?],
+            vec![Number::try_from_input(7, "58")?],
+            vec![Number::try_from_input(2, "592")?],
+            vec![Number::try_from_input(6, "755")?],
+            vec![],
+            vec![
+                Number::try_from_input(1, "664")?,
+                Number::try_from_input(5, "598")?,
+            ],
+        ];
+        let symbols = vec![
+            Symbol {
+                position: (1, 3),
+                sym: '*',
+            },
+            Symbol {
+                position: (3, 6),
+                sym: '#',
+            },
+            Symbol {
+                position: (4, 3),
+                sym: '*',
+            },
+            Symbol {
+                position: (5, 5),
+                sym: '+',
+            },
+            Symbol {
+                position: (8, 3),
+                sym: '$',
+            },
+            Symbol {
+                position: (8, 5),
+                sym: '*',
+            },
+        ];
+
+        Ok((numbers, symbols))
+    }
+
+    #[test]
+    fn test_parse_sample() -> Result<()> {
+        assert_eq!(parse_input(SAMPLE)?, parsed_sample()?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_unicode() -> Result<()> {
+        let (numbers, symbols) = parse_input("é.467\n..→..\n٣35.ü")?;
+
+        assert_eq!(
+            numbers,
+            vec![
+                vec![Number::try_from_input(2, "467")?],
+                vec![],
+                vec![Number::try_from_input(1, "35")?],
+            ]
+        );
+        assert_eq!(
+            symbols
+                .iter()
+                .map(|symbol| symbol.position)
+                .collect::<Vec<_>>(),
+            vec![(0, 0), (1, 2), (2, 0), (2, 4)]
+        );
+        assert_eq!(
+            symbols.iter().map(|symbol| symbol.sym).collect::<String>(),
+            "é→٣ü"
+        );
+        assert_eq!(
+            part_1(
+                &numbers,
+                &symbols,
+                PartCounting::PerSymbol,
+                Neighbourhood::default()
+            )?,
+            467 + 35 + 35
+        );
+        assert_eq!(
+            part_2(
+                &numbers,
+                &symbols,
+                &"→:=2:product".parse()?,
+                Neighbourhood::default()
+            )?,
+            467 * 35
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn columns_count_characters() -> Result<()> {
+        // In bytes, the number would be three columns right of the symbol.
+        let (numbers, symbols) = parse_input("ééé1\n...*")?;
+
+        assert_eq!(numbers[0], vec![Number::try_from_input(3, "1")?]);
+        assert_eq!(symbols[3].position, (1, 3));
+        assert_eq!(
+            part_1(
+                &numbers,
+                &symbols[3..],
+                PartCounting::PerSymbol,
+                Neighbourhood::default()
+            )?,
+            1
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_signed() -> Result<()> {
+        let input = "..-42*\n-7.3-1\n.--5..";
+        let (numbers, symbols) = parse_signed_input(input, Minus::Sign)?;
+
+        assert_eq!(
+            numbers,
+            vec![
+                vec![Number::try_from_input(2, "-42")?],
+                vec![
+                    Number::try_from_input(0, "-7")?,
+                    Number::try_from_input(3, "3")?,
+                    Number::try_from_input(4, "-1")?,
+                ],
+                vec![Number::try_from_input(2, "-5")?],
+            ]
+        );
+        assert_eq!(
+            symbols,
+            vec![
+                Symbol {
+                    position: (0, 5),
+                    sym: '*'
+                },
+                Symbol {
+                    position: (2, 1),
+                    sym: '-'
+                },
+            ]
+        );
+        assert_eq!(
+            part_1(
+                &numbers,
+                &symbols,
+                PartCounting::PerSymbol,
+                Neighbourhood::default()
+            )?,
+            -42 - 1 - 7 - 5
+        );
+        assert_eq!(
+            part_2(
+                &numbers,
+                &symbols,
+                &GearSpec::default(),
+                Neighbourhood::default()
+            )?,
+            42
+        );
+
+        let (numbers, symbols) = parse_signed_input(input, Minus::SignAndSymbol)?;
+        assert_eq!(
+            symbols
+                .iter()
+                .map(|symbol| symbol.position)
+                .collect::<Vec<_>>(),
+            vec![(0, 2), (0, 5), (1, 0), (1, 4), (2, 1), (2, 2)]
+        );
+        // Each sign sees the numbers around it, but not its own number.
+        assert_eq!(
+            part_1(
+                &numbers,
+                &symbols,
+                PartCounting::PerSymbol,
+                Neighbourhood::default()
+            )?,
+            (-7 + 3) + (-42 - 1) + (-42 + 3 - 5) + (-7 - 5) + (-7 + 3)
+        );
+        let (numbers, symbols) = parse_signed_input("-42......", Minus::SignAndSymbol)?;
+        for counting in [PartCounting::PerSymbol, PartCounting::Unique] {
+            assert_eq!(
+                part_1(&numbers, &symbols, counting, Neighbourhood::default())?,
+                0
+            );
+        }
+
+        let (numbers, symbols) = parse_signed_input(input, Minus::Symbol)?;
+        let (unsigned_numbers, unsigned_symbols) = parse_input(input)?;
+        assert_eq!(symbols, unsigned_symbols);
+        assert_eq!(
+            numbers
+                .iter()
+                .flatten()
+                .map(|number| number.value)
+                .collect::<Vec<_>>(),
+            unsigned_numbers
+                .iter()
+                .flatten()
+                .map(|number| number.value as isize)
+                .collect::<Vec<_>>()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_minus_modes() -> Result<()> {
+        for minus in [Minus::Symbol, Minus::Sign, Minus::SignAndSymbol] {
+            assert_eq!(minus.to_string().parse::<Minus>()?, minus);
+        }
+        assert!("negative".parse::<Minus>().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_part_1() -> Result<()> {
+        let (numbers, symbols) = parsed_sample()?;
+        assert_eq!(
+            part_1(
+                &numbers,
+                &symbols,
+                PartCounting::PerSymbol,
+                Neighbourhood::default()
+            )?,
+            4361
+        );
+        assert_eq!(
+            part_1(
+                &numbers,
+                &symbols,
+                PartCounting::Unique,
+                Neighbourhood::default()
+            )?,
+            4361
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_part_1_shared_numbers() -> Result<()> {
+        let (numbers, symbols) = parse_input("..*..\n.123.\n....#\n45...\n+....")?;
+
+        assert_eq!(
+            part_1(
+                &numbers,
+                &symbols,
+                PartCounting::PerSymbol,
+                Neighbourhood::default()
+            )?,
+            123 * 2 + 45
+        );
+        assert_eq!(
+            part_1(
+                &numbers,
+                &symbols,
+                PartCounting::Unique,
+                Neighbourhood::default()
+            )?,
+            123 + 45
+        );
+        assert_eq!(
+            shared_numbers(&numbers, &symbols, Neighbourhood::default()),
+            vec![(1, &Number::try_from_input(1, "123")?, 2)]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_part_2() -> Result<()> {
+        let (numbers, symbols) = parsed_sample()?;
+        assert_eq!(
+            part_2(
+                &numbers,
+                &symbols,
+                &GearSpec::default(),
+                Neighbourhood::default()
+            )?,
+            467835
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_part_2_does_not_wrap() -> Result<()> {
+        let numbers: Vec<Vec<Number>> = vec![vec![
+            Number::try_from_input(0, &usize::MAX.to_string())?,
+            Number::try_from_input(21, "2")?,
+        ]];
+        let symbols = vec![Symbol {
+            position: (0, 20),
+            sym: '*',
+        }];
+
+        assert_eq!(
+            part_2(
+                &numbers,
+                &symbols,
+                &GearSpec::default(),
+                Neighbourhood::default()
+            )?,
+            usize::MAX as Answer * 2
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_part_2_gear_specs() -> Result<()> {
+        let (numbers, symbols) = parsed_sample()?;
+        let part_2_with = |spec: &str| -> Result<Answer> {
+            Ok(part_2(
+                &numbers,
+                &symbols,
+                &spec.parse()?,
+                Neighbourhood::default(),
+            )?)
+        };
+
+        // Every symbol of the sample touches one or two numbers.
+        assert_eq!(part_2_with("*:=1:product")?, 617);
+        assert_eq!(part_2_with("*:>=1:sum")?, 467 + 35 + 617 + 755 + 598);
+        assert_eq!(
+            part_2_with("*#+$:<=2:max")?,
+            467 + 633 + 617 + 592 + 664 + 755
+        );
+        assert_eq!(part_2_with("#:=2:product")?, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_neighbourhoods() -> Result<()> {
+        // Numbers around the symbol at (2, 3), at several distances.
+        let (numbers, symbols) = parse_input("1......\n..20...\n...*...\n.....3.\n....400")?;
+        let part_1_with = |neighbourhood: &str| -> Result<Answer> {
+            Ok(part_1(
+                &numbers,
+                &symbols,
+                PartCounting::PerSymbol,
+                neighbourhood.parse()?,
+            )?)
+        };
+
+        assert_eq!(part_1_with("orthogonal")?, 20);
+        assert_eq!(part_1_with("chebyshev:1")?, 20);
+        assert_eq!(part_1_with("chebyshev:2")?, 20 + 3 + 400);
+        assert_eq!(part_1_with("manhattan:1")?, 20);
+        assert_eq!(part_1_with("manhattan:2")?, 20);
+        assert_eq!(part_1_with("manhattan:3")?, 20 + 3 + 400);
+        assert_eq!(part_1_with("chebyshev:0")?, 0);
+
+        let gear = "*:=3:product".parse()?;
+        assert_eq!(
+            part_2(&numbers, &symbols, &gear, "manhattan:3".parse()?)?,
+            20 * 3 * 400
+        );
+        assert_eq!(part_2(&numbers, &symbols, &gear, "orthogonal".parse()?)?, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_adjacent_numbers_match_linear_scan() -> Result<()> {
+        let (numbers, _) = parse_input("12..345.6\n.*..#...7\n8.90..*..\n..$.1234.\n5.67....*")?;
+
+        for neighbourhood in [
+            Neighbourhood::Orthogonal,
+            Neighbourhood::Chebyshev(1),
+            Neighbourhood::Chebyshev(3),
+            Neighbourhood::Manhattan(2),
+        ] {
+            for (line, column) in
+                (0..5usize).flat_map(|line| (0..9).map(move |column| (line, column)))
+            {
+                let linear: Vec<(usize, &Number)> = numbers
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(line_number, numbers_in_line)| {
+                        numbers_in_line
+                            .iter()
+                            .map(move |number| (line_number, number))
+                    })
+                    .filter(|(line_number, number)| {
+                        number.is_adjacent_to(line.abs_diff(*line_number), column, neighbourhood)
+                    })
+                    .collect();
+
+                assert_eq!(
+                    adjacent_numbers(&numbers, (line, column), neighbourhood).collect::<Vec<_>>(),
+                    linear,
+                    "{neighbourhood} at {:?}",
+                    (line, column)
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
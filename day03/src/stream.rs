@@ -0,0 +1,276 @@
+//! Streaming evaluation of a schematic, keeping only the lines around the
+//! symbols being evaluated.
+//!
+//! A symbol can be evaluated once all the lines of its neighbourhood are known:
+//! with a radius r, the window holds 2r + 1 lines, three for the puzzle's rules.
+use std::collections::{HashSet, VecDeque};
+use std::io::BufRead;
+
+use aoc_lib::{Result, answer::checked_add, math::Integer};
+
+use crate::{
+    gear::GearSpec,
+    neighbourhood::Neighbourhood,
+    schematic::{
+        Minus, Number, PartCounting, Symbol, Value, adjacent_numbers, compute_gear_ratio, parse,
+        sum_numbers_around_symbol,
+    },
+};
+
+/// Line of the window.
+#[derive(Debug)]
+struct WindowLine<V> {
+    numbers: Vec<Number<V>>,
+    /// Symbols of the line, positioned on line 0.
+    symbols: Vec<Symbol>,
+    /// Positions of the numbers already counted, with [`PartCounting::Unique`].
+    counted: HashSet<usize>,
+}
+
+impl<V> AsRef<[Number<V>]> for WindowLine<V> {
+    fn as_ref(&self) -> &[Number<V>] {
+        &self.numbers
+    }
+}
+
+/// Evaluates parts 1 and 2 line by line, with the same results as
+/// [`part_1`](crate::schematic::part_1) and [`part_2`](crate::schematic::part_2)
+/// on the whole schematic.
+#[derive(Debug)]
+pub struct StreamingEvaluator<V: Value = usize> {
+    counting: PartCounting,
+    gear: GearSpec,
+    neighbourhood: Neighbourhood,
+    /// How a '-' directly before digits is read.
+    minus: Minus,
+    /// Last lines read, the oldest first.
+    window: VecDeque<WindowLine<V>>,
+    /// Number of lines read.
+    lines_read: usize,
+    /// Number of lines whose symbols were evaluated.
+    lines_evaluated: usize,
+    part_1: V::Total,
+    part_2: V::Total,
+}
+
+impl StreamingEvaluator {
+    pub fn new(counting: PartCounting, gear: GearSpec, neighbourhood: Neighbourhood) -> Self {
+        Self::with_minus(counting, gear, neighbourhood, Minus::Symbol)
+    }
+}
+
+impl StreamingEvaluator<isize> {
+    /// Evaluator reading a '-' directly before digits as set by `minus`, as
+    /// [`parse_signed_input`](crate::schematic::parse_signed_input).
+    pub fn signed(
+        counting: PartCounting,
+        gear: GearSpec,
+        neighbourhood: Neighbourhood,
+        minus: Minus,
+    ) -> Self {
+        Self::with_minus(counting, gear, neighbourhood, minus)
+    }
+}
+
+impl<V: Value> StreamingEvaluator<V> {
+    fn with_minus(
+        counting: PartCounting,
+        gear: GearSpec,
+        neighbourhood: Neighbourhood,
+        minus: Minus,
+    ) -> Self {
+        Self {
+            counting,
+            gear,
+            neighbourhood,
+            minus,
+            window: VecDeque::new(),
+            lines_read: 0,
+            lines_evaluated: 0,
+            part_1: V::Total::ZERO,
+            part_2: V::Total::ZERO,
+        }
+    }
+
+    /// Sums of parts 1 and 2 for the symbols evaluated so far.
+    pub fn totals(&self) -> (V::Total, V::Total) {
+        (self.part_1, self.part_2)
+    }
+
+    /// Read the next line, evaluating the symbols whose neighbourhood is now known.
+    pub fn push_line(&mut self, line: &str) -> Result<()> {
+        let (mut numbers, symbols) = parse(line, self.minus)?;
+        self.window.push_back(WindowLine {
+            numbers: numbers.pop().unwrap_or_default(),
+            symbols,
+            counted: HashSet::new(),
+        });
+        self.lines_read += 1;
+
+        let radius = self.neighbourhood.radius();
+        if self.lines_read > radius {
+            self.evaluate_next_line()?;
+        }
+        Ok(())
+    }
+
+    /// Evaluate the remaining lines and return the sums of parts 1 and 2.
+    pub fn finish(mut self) -> Result<(V::Total, V::Total)> {
+        while self.lines_evaluated < self.lines_read {
+            self.evaluate_next_line()?;
+        }
+        Ok(self.totals())
+    }
+
+    /// Evaluate a whole schematic, reading it line by line.
+    pub fn evaluate(mut self, input: impl BufRead) -> Result<(V::Total, V::Total)> {
+        for line in input.lines() {
+            self.push_line(&line?)?;
+        }
+        self.finish()
+    }
+
+    /// Evaluate the symbols of the oldest line not evaluated yet, then drop the
+    /// lines no other symbol can reach.
+    fn evaluate_next_line(&mut self) -> Result<()> {
+        let radius = self.neighbourhood.radius();
+        // Absolute number of the first line of the window.
+        let first_line = self.lines_read - self.window.len();
+        let line = self.lines_evaluated - first_line;
+
+        let window = self.window.make_contiguous();
+        let symbols: Vec<Symbol> = window[line]
+            .symbols
+            .iter()
+            .map(|symbol| Symbol {
+                position: (line, symbol.position.1),
+                sym: symbol.sym,
+            })
+            .collect();
+
+        let (mut part_1, mut part_2) = (self.part_1, self.part_2);
+        for symbol in &symbols {
+            match self.counting {
+                PartCounting::PerSymbol => {
+                    let sum = sum_numbers_around_symbol(window, symbol, self.neighbourhood)?;
+                    part_1 = checked_add(part_1, sum, "sum of part numbers")?;
+                }
+                PartCounting::Unique => {
+                    let adjacent: Vec<(usize, Number<V>)> =
+                        adjacent_numbers(window, symbol.position, self.neighbourhood)
+                            .map(|(line_number, number)| (line_number, *number))
+                            .collect();
+                    for (line_number, number) in adjacent {
+                        if window[line_number].counted.insert(number.position) {
+                            part_1 =
+                                checked_add(part_1, number.value.total(), "sum of part numbers")?;
+                        }
+                    }
+                }
+            }
+            if let Some(ratio) = compute_gear_ratio(window, symbol, &self.gear, self.neighbourhood)
+            {
+                part_2 = checked_add(part_2, ratio?, "sum of gear ratios")?;
+            }
+        }
+        (self.part_1, self.part_2) = (part_1, part_2);
+        self.lines_evaluated += 1;
+
+        // The next line to evaluate only needs the lines from radius lines above it.
+        while self.lines_read - self.window.len() + radius < self.lines_evaluated {
+            self.window.pop_front();
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schematic::{parse_input, parse_signed_input, part_1, part_2};
+
+    #[test]
+    fn streaming_matches_batch() -> Result<()> {
+        let inputs = [
+            include_str!("../../inputs/tests/03.txt"),
+            "..*..\n.123.\n....#\n45...\n+....",
+            "1......\n..20...\n...*...\n.....3.\n....400",
+            "*1\n2*",
+            "é.467\n..→..\n٣35.ü",
+            "",
+        ];
+        let gears = ["*:=2:product", "*#+$:>=1:sum", "*:<=3:max"];
+        let neighbourhoods = ["orthogonal", "chebyshev:1", "chebyshev:2", "manhattan:3"];
+
+        for input in inputs {
+            let (numbers, symbols) = parse_input(input)?;
+            for counting in [PartCounting::PerSymbol, PartCounting::Unique] {
+                for gear in gears {
+                    for neighbourhood in neighbourhoods {
+                        let gear: GearSpec = gear.parse()?;
+                        let neighbourhood: Neighbourhood = neighbourhood.parse()?;
+                        let evaluator =
+                            StreamingEvaluator::new(counting, gear.clone(), neighbourhood);
+
+                        assert_eq!(
+                            evaluator.evaluate(input.as_bytes())?,
+                            (
+                                part_1(&numbers, &symbols, counting, neighbourhood)?,
+                                part_2(&numbers, &symbols, &gear, neighbourhood)?
+                            ),
+                            "{input:?} {counting:?} {gear:?} {neighbourhood}"
+                        );
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn signed_streaming_matches_batch() -> Result<()> {
+        let input = "..-42*\n-7.3-1\n.--5..\n9*-8..";
+        let gear: GearSpec = "*-:>=1:sum".parse()?;
+
+        for minus in [Minus::Sign, Minus::SignAndSymbol] {
+            let (numbers, symbols) = parse_signed_input(input, minus)?;
+            for counting in [PartCounting::PerSymbol, PartCounting::Unique] {
+                let neighbourhood = Neighbourhood::default();
+                let evaluator =
+                    StreamingEvaluator::signed(counting, gear.clone(), neighbourhood, minus);
+
+                assert_eq!(
+                    evaluator.evaluate(input.as_bytes())?,
+                    (
+                        part_1(&numbers, &symbols, counting, neighbourhood)?,
+                        part_2(&numbers, &symbols, &gear, neighbourhood)?
+                    ),
+                    "{minus} {counting:?}"
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn window_stays_bounded() -> Result<()> {
+        let mut evaluator = StreamingEvaluator::new(
+            PartCounting::PerSymbol,
+            "*:>=1:sum".parse()?,
+            Neighbourhood::default(),
+        );
+
+        for _ in 0..100 {
+            evaluator.push_line(".1*2.")?;
+            assert!(evaluator.window.len() <= 3);
+        }
+        // Each symbol sees both numbers of its line and of the lines around it.
+        let evaluated = 2 * 3 + 98 * 9;
+        assert_eq!(evaluator.totals(), (evaluated, evaluated));
+        assert_eq!(evaluator.finish()?, (evaluated + 6, evaluated + 6));
+
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
 use aoc_lib::Error as AoCError;
 
-use day07::poker;
+use crate::poker;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum CamelCard {
@@ -43,6 +43,26 @@ impl TryFrom<char> for CamelCard {
     }
 }
 
+impl From<CamelCard> for char {
+    fn from(value: CamelCard) -> Self {
+        match value {
+            CamelCard::Two => '2',
+            CamelCard::Three => '3',
+            CamelCard::Four => '4',
+            CamelCard::Five => '5',
+            CamelCard::Six => '6',
+            CamelCard::Seven => '7',
+            CamelCard::Eight => '8',
+            CamelCard::Nine => '9',
+            CamelCard::Ten => 'T',
+            CamelCard::Jack => 'J',
+            CamelCard::Queen => 'Q',
+            CamelCard::King => 'K',
+            CamelCard::Ace => 'A',
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::camel_cards::CamelCard;
@@ -52,7 +72,7 @@ mod tests {
         let camel_cards_str = "23456789TJQKA";
         let camel_cards: Vec<CamelCard> = camel_cards_str
             .chars()
-            .map(|c| TryInto::<CamelCard>::try_into(c))
+            .map(TryInto::<CamelCard>::try_into)
             .collect::<Result<_, _>>()?;
 
         assert_eq!(
@@ -73,6 +93,10 @@ mod tests {
                 CamelCard::Ace,
             ]
         );
+        assert_eq!(
+            camel_cards.into_iter().map(char::from).collect::<String>(),
+            camel_cards_str
+        );
 
         Ok(())
     }
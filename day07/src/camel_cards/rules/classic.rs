@@ -3,7 +3,7 @@ use std::{
     collections::HashMap,
 };
 
-use day07::poker::{
+use crate::poker::{
     rules::{CompareCards, CompareHands, ComputeHandType},
     HandType,
 };
@@ -63,12 +63,12 @@ impl CompareHands<CamelHand> for ClassicRule {
 mod tests {
     use std::cmp::Ordering;
 
-    use aoc_lib::Result;
-    use day07::poker::{
+    use crate::poker::{
         parse_hand,
         rules::{CompareCards, CompareHands, ComputeHandType},
         HandType,
     };
+    use aoc_lib::Result;
 
     use super::ClassicRule;
     use crate::CamelCard;
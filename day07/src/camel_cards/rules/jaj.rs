@@ -1,6 +1,6 @@
 use std::{cmp::Ordering, marker::PhantomData};
 
-use day07::poker::{
+use crate::poker::{
     rules::{CompareCards, CompareHands, ComputeHandType},
     Card, HandType,
 };
@@ -60,9 +60,7 @@ impl ComputeHandType<CamelHand> for JaJRule<CamelCard> {
                     2 => HandType::FourOfAKind,
                     _ => HandType::FiveOfAKind,
                 },
-                Some(HandType::TwoPair) => match joker_count {
-                    _ => HandType::FullHouse,
-                },
+                Some(HandType::TwoPair) => HandType::FullHouse,
                 Some(HandType::ThreeOfAKind) => match joker_count {
                     1 => HandType::FourOfAKind,
                     _ => HandType::FiveOfAKind,
@@ -97,12 +95,12 @@ mod tests {
 
     use aoc_lib::Result;
 
-    use crate::CamelCard;
-    use day07::poker::{
+    use crate::poker::{
         parse_hand,
         rules::{CompareCards, CompareHands, ComputeHandType},
         HandType,
     };
+    use crate::CamelCard;
 
     use crate::camel_cards::rules::JaJRule;
 
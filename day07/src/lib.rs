@@ -1 +1,142 @@
+pub mod camel_cards;
 pub mod poker;
+pub mod repl;
+
+use aoc_lib::answer::{checked_mul, Answer, TryCheckedIterator};
+use aoc_lib::Result;
+
+use crate::camel_cards::rules::ClassicRule;
+use crate::camel_cards::rules::JaJRule;
+use crate::camel_cards::CamelCard;
+use crate::poker::rules::CompareHands;
+
+pub type CamelHand = Vec<CamelCard>;
+pub type Bid = usize;
+
+pub fn parse_input(input: &str) -> Result<Vec<(CamelHand, Bid)>> {
+    input
+        .lines()
+        .filter_map(|line| line.split_once(' '))
+        .map(|(hand_str, bid_str)| Ok((poker::parse_hand(hand_str)?, bid_str.parse()?)))
+        .collect::<Result<_>>()
+}
+
+// Computes the total winnings of hands sorted by increasing strength.
+fn total_winnings(sorted_input: &[(CamelHand, Bid)]) -> Result<Answer> {
+    sorted_input
+        .iter()
+        .enumerate()
+        .map(|(i, (_, bid))| checked_mul(*bid as Answer, i as Answer + 1, "winnings"))
+        .try_checked_sum("total winnings")
+}
+
+pub fn part_one(input: &mut [(CamelHand, Bid)]) -> Result<Answer> {
+    input.sort_by(|(hand_1, _), (hand_2, _)| ClassicRule::cmp_hands(hand_1, hand_2));
+
+    total_winnings(input)
+}
+
+pub fn part_two(input: &mut [(CamelHand, Bid)]) -> Result<Answer> {
+    input.sort_by(|(hand_1, _), (hand_2, _)| JaJRule::cmp_hands(hand_1, hand_2));
+
+    total_winnings(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse_input;
+    use crate::Bid;
+    use crate::CamelCard;
+    use crate::CamelHand;
+    use crate::{part_one, part_two};
+    use aoc_lib::Result;
+
+    fn example_data() -> Vec<(CamelHand, Bid)> {
+        vec![
+            (
+                vec![
+                    CamelCard::Three,
+                    CamelCard::Two,
+                    CamelCard::Ten,
+                    CamelCard::Three,
+                    CamelCard::King,
+                ],
+                765,
+            ),
+            (
+                vec![
+                    CamelCard::Ten,
+                    CamelCard::Five,
+                    CamelCard::Five,
+                    CamelCard::Jack,
+                    CamelCard::Five,
+                ],
+                684,
+            ),
+            (
+                vec![
+                    CamelCard::King,
+                    CamelCard::King,
+                    CamelCard::Six,
+                    CamelCard::Seven,
+                    CamelCard::Seven,
+                ],
+                28,
+            ),
+            (
+                vec![
+                    CamelCard::King,
+                    CamelCard::Ten,
+                    CamelCard::Jack,
+                    CamelCard::Jack,
+                    CamelCard::Ten,
+                ],
+                220,
+            ),
+            (
+                vec![
+                    CamelCard::Queen,
+                    CamelCard::Queen,
+                    CamelCard::Queen,
+                    CamelCard::Jack,
+                    CamelCard::Ace,
+                ],
+                483,
+            ),
+        ]
+    }
+
+    #[test]
+    fn parse_example() -> Result<()> {
+        let input = include_str!("../../inputs/tests/07.txt");
+
+        assert_eq!(parse_input(input)?, example_data());
+
+        Ok(())
+    }
+
+    #[test]
+    fn part_one_example() -> Result<()> {
+        assert_eq!(part_one(&mut example_data())?, 6440);
+
+        Ok(())
+    }
+
+    #[test]
+    fn part_two_example() -> Result<()> {
+        assert_eq!(part_two(&mut example_data())?, 5905);
+
+        Ok(())
+    }
+
+    #[test]
+    fn winnings_do_not_wrap() -> Result<()> {
+        // Wraps around in a 64-bit usize, but fits in an Answer.
+        let mut input = example_data();
+        input[0].1 = usize::MAX;
+
+        assert_eq!(part_one(&mut input)?, usize::MAX as u128 + 6440 - 765);
+
+        Ok(())
+    }
+}
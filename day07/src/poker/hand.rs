@@ -42,7 +42,7 @@ pub fn parse_hand<T: Hand<Card: TryFrom<char>> + FromIterator<T::Card>>(
 ) -> Result<T, <<T as Hand>::Card as TryFrom<char>>::Error> {
     input
         .chars()
-        .map(|c| TryInto::<T::Card>::try_into(c))
+        .map(TryInto::<T::Card>::try_into)
         .collect::<Result<_, _>>()
 }
 
@@ -0,0 +1,144 @@
+//! Interactive exploration of parsed hands, started with `cargo run -- repl [input]`
+//! or `aoc repl 7 [input]` from the workspace runner.
+use std::collections::BTreeMap;
+
+use aoc_lib::answer::{Answer, CheckedIterator};
+use aoc_lib::repl::{parse_arg, Repl};
+use aoc_lib::{Error, Result};
+
+use crate::camel_cards::rules::{ClassicRule, JaJRule};
+use crate::poker::{self, rules::ComputeHandType, HandType};
+use crate::{part_one, part_two, Bid, CamelHand};
+
+struct Game {
+    hands: Vec<(CamelHand, Bid)>,
+}
+
+fn format_hand(hand: &CamelHand) -> String {
+    hand.iter().copied().map(char::from).collect()
+}
+
+fn hand_type(hand: &CamelHand, rule: &str) -> Result<Option<HandType>> {
+    match rule {
+        "classic" => Ok(ClassicRule::hand_type(hand)),
+        "jaj" => Ok(JaJRule::hand_type(hand)),
+        _ => Err(Error::ParseError(format!(
+            "unknown rule '{rule}', expected 'classic' or 'jaj'"
+        ))),
+    }
+}
+
+fn show_summary(game: &mut Game, _: &[&str]) -> Result<String> {
+    let total_bid = game
+        .hands
+        .iter()
+        .map(|(_, bid)| *bid as Answer)
+        .checked_sum("total bid")?;
+    let mut summary = format!("{} hands, total bid {total_bid}", game.hands.len());
+
+    for rule in ["classic", "jaj"] {
+        let mut counts = BTreeMap::new();
+        for (hand, _) in game.hands.iter() {
+            *counts.entry(hand_type(hand, rule)?).or_insert(0) += 1;
+        }
+
+        summary.push_str(&format!("\n{rule}:"));
+        for (hand_type, count) in counts {
+            match hand_type {
+                Some(hand_type) => summary.push_str(&format!(" {hand_type:?}={count}")),
+                None => summary.push_str(&format!(" Empty={count}")),
+            }
+        }
+    }
+
+    Ok(summary)
+}
+
+fn show_hand(game: &mut Game, args: &[&str]) -> Result<String> {
+    let index: usize = parse_arg(args, 0, "index")?;
+    let (hand, bid) = game
+        .hands
+        .get(index)
+        .ok_or_else(|| Error::ParseError(format!("no hand {index}")))?;
+
+    Ok(format!(
+        "{} bid {bid}, classic: {:?}, jaj: {:?}",
+        format_hand(hand),
+        ClassicRule::hand_type(hand),
+        JaJRule::hand_type(hand)
+    ))
+}
+
+fn show_type(_: &mut Game, args: &[&str]) -> Result<String> {
+    let hand: String = parse_arg(args, 0, "hand")?;
+    let hand: CamelHand = poker::parse_hand(&hand)?;
+    let rule = args.get(1).copied().unwrap_or("classic");
+
+    Ok(format!("{:?}", hand_type(&hand, rule)?))
+}
+
+/// Build the REPL for the given parsed input.
+pub fn repl(hands: Vec<(CamelHand, Bid)>) -> Repl<impl Sized> {
+    Repl::new("day07> ", Game { hands })
+        .command(
+            "summary",
+            "",
+            "Count hands and hand types under each rule",
+            show_summary,
+        )
+        .command(
+            "hand",
+            "<index>",
+            "Show a hand of the input with its types",
+            show_hand,
+        )
+        .command(
+            "type",
+            "<hand> [classic|jaj]",
+            "Compute the type of any hand under a rule",
+            show_type,
+        )
+        // Parts sort the hands in place, work on a copy to keep input indices.
+        .command("part1", "", "Run part 1", |game, _| {
+            Ok(part_one(&mut game.hands.clone())?.to_string())
+        })
+        .command("part2", "", "Run part 2", |game, _| {
+            Ok(part_two(&mut game.hands.clone())?.to_string())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_input;
+
+    fn run(repl: &mut Repl<impl Sized>, line: &str) -> Result<String> {
+        repl.execute(line).expect("command should not quit")
+    }
+
+    #[test]
+    fn repl_queries() -> Result<()> {
+        let mut repl = repl(parse_input(include_str!("../../inputs/tests/07.txt"))?);
+
+        assert_eq!(
+            run(&mut repl, "hand 1")?,
+            "T55J5 bid 684, classic: Some(ThreeOfAKind), jaj: Some(FourOfAKind)"
+        );
+        assert_eq!(run(&mut repl, "type KTJJT jaj")?, "Some(FourOfAKind)");
+        assert_eq!(run(&mut repl, "type KTJJT")?, "Some(TwoPair)");
+        assert!(run(&mut repl, "type KTJJT poker").is_err());
+        assert!(run(&mut repl, "type KTXJT").is_err());
+        assert!(run(&mut repl, "type").is_err());
+        assert_eq!(
+            run(&mut repl, "summary")?,
+            "5 hands, total bid 2180\n\
+             classic: OnePair=1 TwoPair=2 ThreeOfAKind=2\n\
+             jaj: OnePair=1 TwoPair=1 FourOfAKind=3"
+        );
+        assert_eq!(run(&mut repl, "part1")?, "6440");
+        assert_eq!(run(&mut repl, "part2")?, "5905");
+        assert!(run(&mut repl, "hand 1")?.starts_with("T55J5"));
+
+        Ok(())
+    }
+}